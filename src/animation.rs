@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use macroquad::prelude::*;
+use nanoserde::{DeJson, DeJsonErr};
+
+/// Aseprite's JSON export, with frames as an array and tags as clips.
+#[derive(DeJson)]
+struct SheetFile {
+    frames: Vec<FrameFile>,
+    meta: MetaFile,
+}
+
+#[derive(DeJson)]
+struct FrameFile {
+    frame: RectFile,
+    duration: u64,
+}
+
+#[derive(DeJson)]
+struct RectFile {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(DeJson)]
+struct MetaFile {
+    #[nserde(rename = "frameTags")]
+    #[nserde(default)]
+    frame_tags: Vec<FrameTagFile>,
+}
+
+#[derive(DeJson)]
+struct FrameTagFile {
+    name: String,
+    from: usize,
+    to: usize,
+    #[nserde(default)]
+    direction: String,
+    /// How many times the tag plays before holding its last frame; missing loops forever.
+    #[nserde(default)]
+    repeat: String,
+}
+
+struct Frame {
+    source: Rect,
+    duration: Duration,
+}
+
+/// A named run of frames, already in the order its direction plays them.
+struct Clip {
+    frames: Vec<usize>,
+    repeat: Option<u32>,
+}
+
+/// A texture cut into timed frames, with named clips over them.
+pub struct SpriteSheet {
+    texture: Texture2D,
+    frames: Vec<Frame>,
+    clips: HashMap<String, Clip>,
+}
+
+impl SpriteSheet {
+    pub fn from_json(json: &str, texture: Texture2D) -> Result<Self, SpriteSheetError> {
+        let file = SheetFile::deserialize_json(json).map_err(SpriteSheetError::Parse)?;
+        if file.frames.is_empty() {
+            return Err(SpriteSheetError::NoFrames);
+        }
+        let frames = file
+            .frames
+            .iter()
+            .map(|frame| Frame {
+                source: Rect::new(frame.frame.x, frame.frame.y, frame.frame.w, frame.frame.h),
+                duration: Duration::from_millis(frame.duration),
+            })
+            .collect::<Vec<_>>();
+        let mut clips = HashMap::new();
+        for tag in file.meta.frame_tags {
+            let clip = Clip::from_tag(&tag, frames.len())?;
+            clips.insert(tag.name, clip);
+        }
+        Ok(Self {
+            texture,
+            frames,
+            clips,
+        })
+    }
+
+    /// A sheet whose only frame is the whole texture, shown for every clip.
+    pub fn still(texture: Texture2D, size: Vec2) -> Self {
+        Self {
+            texture,
+            frames: vec![Frame {
+                source: Rect::new(0.0, 0.0, size.x, size.y),
+                duration: Duration::ZERO,
+            }],
+            clips: HashMap::new(),
+        }
+    }
+
+    pub fn require_clips(&self, names: &[&str]) -> Result<(), SpriteSheetError> {
+        match names.iter().find(|name| !self.clips.contains_key(**name)) {
+            Some(name) => Err(SpriteSheetError::MissingClip(name.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    pub fn texture(&self) -> Texture2D {
+        self.texture
+    }
+
+    /// Size of the first frame, which every frame of a character sheet shares.
+    pub fn frame_size(&self) -> Vec2 {
+        self.frames[0].source.size()
+    }
+
+    /// The part of the texture `animation` shows; clips the sheet doesn't have show the first frame.
+    pub fn source(&self, animation: &Animation) -> Rect {
+        let frame = self
+            .clips
+            .get(animation.clip)
+            .map_or(0, |clip| self.frame_index(clip, animation.elapsed));
+        self.frames[frame].source
+    }
+
+    /// Whether a clip that plays a set number of times has played out.
+    pub fn is_finished(&self, animation: &Animation) -> bool {
+        match self.clips.get(animation.clip) {
+            Some(clip) => clip
+                .repeat
+                .is_some_and(|repeat| animation.elapsed >= self.clip_duration(clip) * repeat),
+            None => true,
+        }
+    }
+
+    fn clip_duration(&self, clip: &Clip) -> Duration {
+        clip.frames
+            .iter()
+            .map(|&frame| self.frames[frame].duration)
+            .sum()
+    }
+
+    fn frame_index(&self, clip: &Clip, elapsed: Duration) -> usize {
+        let duration = self.clip_duration(clip);
+        let last = clip.frames[clip.frames.len() - 1];
+        if duration.is_zero() {
+            return clip.frames[0];
+        }
+        if clip
+            .repeat
+            .is_some_and(|repeat| elapsed >= duration * repeat)
+        {
+            return last;
+        }
+        let mut time = Duration::from_nanos((elapsed.as_nanos() % duration.as_nanos()) as u64);
+        for &frame in &clip.frames {
+            match time.checked_sub(self.frames[frame].duration) {
+                Some(rest) => time = rest,
+                None => return frame,
+            }
+        }
+        last
+    }
+}
+
+impl Clip {
+    fn from_tag(tag: &FrameTagFile, frame_count: usize) -> Result<Self, SpriteSheetError> {
+        if tag.from > tag.to || tag.to >= frame_count {
+            return Err(SpriteSheetError::FrameOutOfRange {
+                clip: tag.name.clone(),
+                from: tag.from,
+                to: tag.to,
+                frame_count,
+            });
+        }
+        let forward = tag.from..=tag.to;
+        let frames = match tag.direction.as_str() {
+            "" | "forward" => forward.collect(),
+            "reverse" => forward.rev().collect(),
+            // The end frames play once per round trip
+            "pingpong" => forward
+                .clone()
+                .chain(
+                    forward
+                        .rev()
+                        .skip(1)
+                        .take((tag.to - tag.from).saturating_sub(1)),
+                )
+                .collect(),
+            direction => {
+                return Err(SpriteSheetError::UnknownDirection {
+                    clip: tag.name.clone(),
+                    direction: direction.into(),
+                })
+            }
+        };
+        let repeat = match tag.repeat.as_str() {
+            "" => None,
+            repeat => Some(
+                repeat
+                    .parse()
+                    .ok()
+                    .filter(|repeat| *repeat > 0)
+                    .ok_or_else(|| SpriteSheetError::InvalidRepeat {
+                        clip: tag.name.clone(),
+                        repeat: repeat.into(),
+                    })?,
+            ),
+        };
+        Ok(Self { frames, repeat })
+    }
+}
+
+/// Which clip is playing and for how long; the sheet turns that into a frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Animation {
+    clip: &'static str,
+    elapsed: Duration,
+}
+
+impl Animation {
+    pub fn new(clip: &'static str) -> Self {
+        Self {
+            clip,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn clip(&self) -> &'static str {
+        self.clip
+    }
+
+    /// Starts `clip` from its first frame, unless it's already playing.
+    pub fn play(&mut self, clip: &'static str) {
+        if self.clip != clip {
+            *self = Self::new(clip);
+        }
+    }
+
+    pub fn advance(&mut self, frame_time: Duration) {
+        self.elapsed += frame_time;
+    }
+}
+
+#[derive(Debug)]
+pub enum SpriteSheetError {
+    Parse(DeJsonErr),
+    NoFrames,
+    FrameOutOfRange {
+        clip: String,
+        from: usize,
+        to: usize,
+        frame_count: usize,
+    },
+    UnknownDirection {
+        clip: String,
+        direction: String,
+    },
+    InvalidRepeat {
+        clip: String,
+        repeat: String,
+    },
+    MissingClip(String),
+}
+
+impl fmt::Display for SpriteSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpriteSheetError::Parse(err) => write!(f, "can't parse sprite sheet: {}", err),
+            SpriteSheetError::NoFrames => write!(f, "sprite sheet has no frames"),
+            SpriteSheetError::FrameOutOfRange {
+                clip,
+                from,
+                to,
+                frame_count,
+            } => write!(
+                f,
+                "clip \"{}\" spans frames {}..={}, but the sheet has {} frames",
+                clip, from, to, frame_count
+            ),
+            SpriteSheetError::UnknownDirection { clip, direction } => write!(
+                f,
+                "clip \"{}\" has direction \"{}\", expected forward, reverse or pingpong",
+                clip, direction
+            ),
+            SpriteSheetError::InvalidRepeat { clip, repeat } => write!(
+                f,
+                "clip \"{}\" has repeat \"{}\", expected a positive count",
+                clip, repeat
+            ),
+            SpriteSheetError::MissingClip(clip) => {
+                write!(f, "sprite sheet has no clip \"{}\"", clip)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpriteSheetError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sheet(tags: &str) -> SpriteSheet {
+        let frame = |x, duration| {
+            format!(
+                r#"{{"frame": {{"x": {}, "y": 0, "w": 10, "h": 20}}, "duration": {}}}"#,
+                x, duration
+            )
+        };
+        let json = format!(
+            r#"{{"frames": [{}, {}, {}], "meta": {{"frameTags": [{}]}}}}"#,
+            frame(0, 100),
+            frame(10, 100),
+            frame(20, 200),
+            tags
+        );
+        SpriteSheet::from_json(&json, Texture2D::empty()).unwrap()
+    }
+
+    fn frame_x_at(sheet: &SpriteSheet, clip: &'static str, millis: u64) -> f32 {
+        let mut animation = Animation::new(clip);
+        animation.advance(Duration::from_millis(millis));
+        sheet.source(&animation).x
+    }
+
+    #[test]
+    fn should_loop_clip_by_frame_durations() {
+        let sheet = sheet(r#"{"name": "walk", "from": 0, "to": 2}"#);
+        let frames =
+            [0, 99, 100, 200, 399, 400, 550].map(|millis| frame_x_at(&sheet, "walk", millis));
+        assert_eq!(frames, [0.0, 0.0, 10.0, 20.0, 20.0, 0.0, 10.0]);
+    }
+
+    #[test]
+    fn should_hold_last_frame_once_repeats_are_done() {
+        let sheet = sheet(r#"{"name": "land", "from": 0, "to": 1, "repeat": "2"}"#);
+        let mut animation = Animation::new("land");
+        animation.advance(Duration::from_millis(350));
+        assert!(!sheet.is_finished(&animation));
+        assert_eq!(sheet.source(&animation).x, 10.0);
+        animation.advance(Duration::from_millis(50));
+        assert!(sheet.is_finished(&animation));
+        assert_eq!(frame_x_at(&sheet, "land", 1000), 10.0);
+    }
+
+    #[test]
+    fn should_play_reverse_and_pingpong() {
+        let sheet = sheet(
+            r#"{"name": "back", "from": 0, "to": 2, "direction": "reverse"},
+               {"name": "bounce", "from": 0, "to": 2, "direction": "pingpong"}"#,
+        );
+        let back = [0, 200, 300].map(|millis| frame_x_at(&sheet, "back", millis));
+        assert_eq!(back, [20.0, 10.0, 0.0]);
+        let bounce = [0, 100, 200, 400, 500].map(|millis| frame_x_at(&sheet, "bounce", millis));
+        assert_eq!(bounce, [0.0, 10.0, 20.0, 10.0, 0.0]);
+    }
+
+    #[test]
+    fn should_restart_only_on_a_different_clip() {
+        let mut animation = Animation::new("walk");
+        animation.advance(Duration::from_millis(100));
+        animation.play("walk");
+        assert_eq!(animation.elapsed, Duration::from_millis(100));
+        animation.play("idle");
+        assert_eq!(animation, Animation::new("idle"));
+    }
+
+    #[test]
+    fn should_reject_clip_past_last_frame() {
+        let json = r#"{"frames": [{"frame": {"x": 0, "y": 0, "w": 1, "h": 1}, "duration": 1}],
+                       "meta": {"frameTags": [{"name": "idle", "from": 0, "to": 1}]}}"#;
+        let err = SpriteSheet::from_json(json, Texture2D::empty())
+            .err()
+            .unwrap();
+        assert!(matches!(err, SpriteSheetError::FrameOutOfRange { .. }));
+    }
+
+    #[test]
+    fn should_report_missing_clip() {
+        let sheet = sheet(r#"{"name": "idle", "from": 0, "to": 0}"#);
+        assert!(sheet.require_clips(&["idle"]).is_ok());
+        let err = sheet.require_clips(&["idle", "swim"]).err().unwrap();
+        assert_eq!(err.to_string(), "sprite sheet has no clip \"swim\"");
+    }
+}
@@ -1,5 +1,12 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
 use macroquad::prelude::*;
 
+/// How often `AssetWatcher` checks the file's modification time.
+pub const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
 pub struct AssetsServer {
     base_path: String,
 }
@@ -11,15 +18,168 @@ impl AssetsServer {
         }
     }
 
-    pub async fn load_string(&self, path: &str) -> Result<String, FileError> {
-        load_string(&self.join(path)).await
+    pub async fn load_string(&self, path: &str) -> Result<String, AssetError> {
+        let path = self.join(path);
+        load_string(&path)
+            .await
+            .map_err(|cause| AssetError::Io { path, cause })
     }
 
     fn join(&self, path: &str) -> String {
         [self.base_path.as_str(), path].join("/")
     }
 
-    pub async fn load_texture(&self, path: &str) -> Result<Texture2D, FileError> {
-        load_texture(&self.join(path)).await
+    pub async fn load_texture(&self, path: &str) -> Result<Texture2D, AssetError> {
+        let path = self.join(path);
+        load_texture(&path)
+            .await
+            .map_err(|cause| AssetError::Io { path, cause })
+    }
+
+    /// Watches an asset on disk so it can be reloaded while the game runs.
+    pub fn watch(&self, path: &str) -> AssetWatcher {
+        AssetWatcher::new(self.join(path).into())
+    }
+}
+
+#[derive(Debug)]
+pub enum AssetError {
+    Io {
+        path: String,
+        cause: FileError,
+    },
+    Parse {
+        path: String,
+        cause: String,
+    },
+    /// The map refers to a tileset or texture that wasn't loaded alongside it.
+    TilesetMismatch {
+        path: String,
+        cause: String,
+    },
+    MissingObject {
+        path: String,
+        name: String,
+    },
+    /// No layer of the map plays `role`; `layers` lists the tile layers it does have.
+    MissingLayer {
+        path: String,
+        role: String,
+        layers: Vec<String>,
+    },
+    InvalidProperty {
+        path: String,
+        cause: String,
+    },
+}
+
+impl AssetError {
+    pub fn parse(path: &str, cause: impl fmt::Display) -> Self {
+        AssetError::Parse {
+            path: path.into(),
+            cause: cause.to_string(),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        match self {
+            AssetError::Io { path, .. }
+            | AssetError::Parse { path, .. }
+            | AssetError::TilesetMismatch { path, .. }
+            | AssetError::MissingObject { path, .. }
+            | AssetError::MissingLayer { path, .. }
+            | AssetError::InvalidProperty { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetError::Io { path, cause } => write!(f, "can't read {}: {}", path, cause.kind),
+            AssetError::Parse { path, cause } => write!(f, "can't parse {}: {}", path, cause),
+            AssetError::TilesetMismatch { path, cause } => {
+                write!(f, "tileset mismatch in {}: {}", path, cause)
+            }
+            AssetError::MissingObject { path, name } => {
+                write!(f, "{} has no object named \"{}\"", path, name)
+            }
+            AssetError::MissingLayer { path, role, layers } => write!(
+                f,
+                "{} has no layer with {}, only {}",
+                path,
+                role,
+                layers.join(", ")
+            ),
+            AssetError::InvalidProperty { path, cause } => {
+                write!(f, "invalid property in {}: {}", path, cause)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AssetError {}
+
+/// Polls the modification time of one asset file.
+pub struct AssetWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    since_poll: Duration,
+}
+
+impl AssetWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified_time(&path);
+        Self {
+            path,
+            modified,
+            since_poll: Duration::ZERO,
+        }
+    }
+
+    /// Returns true once per change, checking the file at most every `WATCH_INTERVAL`.
+    pub fn poll(&mut self, frame_time: Duration) -> bool {
+        self.since_poll += frame_time;
+        if self.since_poll < WATCH_INTERVAL {
+            return false;
+        }
+        self.since_poll = Duration::ZERO;
+
+        let modified = modified_time(&self.path);
+        let changed = modified.is_some() && modified != self.modified;
+        self.modified = modified;
+        changed
+    }
+
+    /// Reads the file synchronously, so a reload can happen in the middle of a tick.
+    pub fn read_string(&self) -> std::io::Result<String> {
+        std::fs::read_to_string(&self.path)
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_report_each_change_once() {
+        let path = std::env::temp_dir().join(format!("duck_watch_{}.txt", std::process::id()));
+        std::fs::write(&path, "before").unwrap();
+        let mut watcher = AssetWatcher::new(path.clone());
+        assert!(!watcher.poll(WATCH_INTERVAL));
+
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert!(!watcher.poll(WATCH_INTERVAL / 2));
+        assert!(watcher.poll(WATCH_INTERVAL));
+        assert!(!watcher.poll(WATCH_INTERVAL));
+        std::fs::remove_file(&path).unwrap();
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::LinkedList;
 
+#[derive(Clone)]
 pub struct FollowBuffer {
     list: LinkedList<f32>,
     capacity: usize,
@@ -20,11 +21,19 @@ impl FollowBuffer {
         }
     }
 
-    pub fn mean(&self) -> f32 {
-        self.list.iter().sum::<f32>() / self.list.len() as f32
+    /// `None` until something is pushed.
+    pub fn mean(&self) -> Option<f32> {
+        if self.list.is_empty() {
+            return None;
+        }
+        Some(self.list.iter().sum::<f32>() / self.list.len() as f32)
     }
 
     pub fn last(&self) -> Option<f32> {
         self.list.back().cloned()
     }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
 }
@@ -5,8 +5,16 @@ use self::follow_buffer::FollowBuffer;
 
 mod follow_buffer;
 
-const FOLLOW_X_BUFFER_CAPACITY: usize = 75;
-const FOLLOW_Y_BUFFER_CAPACITY: usize = 25;
+/// Counted in physics steps, so the smoothing lasts as long whatever the frame rate.
+const FOLLOW_X_BUFFER_CAPACITY: usize = 150;
+const FOLLOW_Y_BUFFER_CAPACITY: usize = 50;
+
+/// The camera's follow history, so a respawn can resume it instead of snapping.
+#[derive(Clone)]
+pub struct CameraState {
+    x_follow_buffer: FollowBuffer,
+    y_follow_buffer: FollowBuffer,
+}
 
 pub struct Camera {
     map_size: Vec2,
@@ -31,21 +39,38 @@ impl Camera {
         self.viewport
     }
 
-    pub fn update(&mut self, focus_position: Vec2) {
-        self.update_follow_buffers(focus_position);
-        self.update_viewport(focus_position);
+    /// Forgets the focus history so the viewport snaps to the focus position.
+    pub fn reset(&mut self) {
+        self.x_follow_buffer.clear();
+        self.y_follow_buffer.clear();
+    }
+
+    pub fn state(&self) -> CameraState {
+        CameraState {
+            x_follow_buffer: self.x_follow_buffer.clone(),
+            y_follow_buffer: self.y_follow_buffer.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, state: &CameraState) {
+        self.x_follow_buffer = state.x_follow_buffer.clone();
+        self.y_follow_buffer = state.y_follow_buffer.clone();
     }
 
-    fn update_follow_buffers(&mut self, focus_position: Vec2) {
+    /// Records where the focus is after a physics step.
+    pub fn follow(&mut self, focus_position: Vec2) {
         if self.x_follow_buffer.last() != Some(focus_position.x) {
             self.x_follow_buffer.push(focus_position.x)
         }
         self.y_follow_buffer.push(focus_position.y);
     }
 
-    fn update_viewport(&mut self, focus_position: Vec2) {
-        let x_offset = focus_position.x - self.x_follow_buffer.mean();
-        let y_offset = (self.y_follow_buffer.mean() - focus_position.y) / 2.;
+    /// Places the viewport around `focus_position`, trailing the followed history.
+    pub fn update_viewport(&mut self, focus_position: Vec2) {
+        let x_mean = self.x_follow_buffer.mean().unwrap_or(focus_position.x);
+        let y_mean = self.y_follow_buffer.mean().unwrap_or(focus_position.y);
+        let x_offset = focus_position.x - x_mean;
+        let y_offset = (y_mean - focus_position.y) / 2.;
         let viewport_center = focus_position + vec2(x_offset, y_offset);
         self.viewport = self.get_viewport_bounded_by_window_size(viewport_center);
     }
@@ -57,7 +82,7 @@ impl Camera {
         Rect::new(res.x, res.y, self.viewport_size.x, self.viewport_size.y)
     }
 
-    pub fn focus(&mut self) {
+    pub fn focus(&self) {
         set_camera(&Camera2D::from_display_rect(self.viewport));
     }
 }
@@ -114,4 +139,31 @@ mod tests {
             Rect::new(0., 0., 50., 50.)
         );
     }
+
+    #[test]
+    fn should_center_on_focus_before_following_it() {
+        let mut camera = Camera::new(vec2(1000., 1000.), vec2(100., 100.));
+        camera.update_viewport(vec2(500., 500.));
+        assert_eq!(camera.viewport(), Rect::new(450., 450., 100., 100.));
+    }
+
+    #[test]
+    fn should_resume_from_restored_state() {
+        let mut camera = Camera::new(vec2(1000., 1000.), vec2(100., 100.));
+        let update = |camera: &mut Camera, focus| {
+            camera.follow(focus);
+            camera.update_viewport(focus);
+        };
+        for x in 0..10 {
+            update(&mut camera, vec2(300. + x as f32 * 10., 500.));
+        }
+        let state = camera.state();
+        let viewport = camera.viewport();
+
+        update(&mut camera, vec2(900., 900.));
+        camera.restore(&state);
+        update(&mut camera, vec2(390., 500.));
+
+        assert_eq!(camera.viewport(), viewport);
+    }
 }
@@ -0,0 +1,225 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+use nanoserde::{DeRon, DeRonErr};
+
+use crate::duck_params::DuckParams;
+use crate::save::Progress;
+
+pub const CAMPAIGN_PATH: &str = "campaign.ron";
+
+#[derive(DeRon, Clone, Debug, PartialEq)]
+pub struct LevelInfo {
+    pub name: String,
+    /// Tiled map, relative to the assets directory.
+    pub map: String,
+}
+
+#[derive(DeRon)]
+struct CampaignFile {
+    levels: Vec<LevelInfo>,
+}
+
+/// Ordered levels from `campaign.ron`, which of them the player has finished and how fast.
+#[derive(Default, Debug)]
+pub struct Campaign {
+    levels: Vec<LevelInfo>,
+    completed: Vec<bool>,
+    best_times: Vec<Option<Duration>>,
+    /// Set when progress changes, so the caller knows to save.
+    changed: bool,
+    /// Tuning every level uses instead of `duck.ron`, so a replay plays with what it was
+    /// recorded with.
+    duck_params: Option<DuckParams>,
+}
+
+/// The campaign is shared by the level select screen and the levels it starts.
+pub type SharedCampaign = Rc<RefCell<Campaign>>;
+
+impl Campaign {
+    pub fn new(levels: Vec<LevelInfo>) -> Self {
+        let completed = vec![false; levels.len()];
+        let best_times = vec![None; levels.len()];
+        Self {
+            levels,
+            completed,
+            best_times,
+            changed: false,
+            duck_params: None,
+        }
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Self, CampaignError> {
+        let file = CampaignFile::deserialize_ron(ron).map_err(CampaignError::Parse)?;
+        if file.levels.is_empty() {
+            return Err(CampaignError::NoLevels);
+        }
+        Ok(Self::new(file.levels))
+    }
+
+    pub fn levels(&self) -> &[LevelInfo] {
+        &self.levels
+    }
+
+    pub fn level(&self, index: usize) -> Option<&LevelInfo> {
+        self.levels.get(index)
+    }
+
+    pub fn is_completed(&self, index: usize) -> bool {
+        self.completed.get(index).copied().unwrap_or(false)
+    }
+
+    /// The first level is always open; every other one opens once the previous is completed.
+    pub fn is_unlocked(&self, index: usize) -> bool {
+        index < self.levels.len() && (index == 0 || self.is_completed(index - 1))
+    }
+
+    pub fn complete(&mut self, index: usize) {
+        if let Some(completed) = self.completed.get_mut(index) {
+            self.changed |= !*completed;
+            *completed = true;
+        }
+    }
+
+    pub fn best_time(&self, index: usize) -> Option<Duration> {
+        self.best_times.get(index).copied().flatten()
+    }
+
+    /// Keeps `time` if it beats the level's best; returns whether it did.
+    pub fn record_time(&mut self, index: usize, time: Duration) -> bool {
+        match self.best_times.get_mut(index) {
+            Some(best) if best.is_none_or(|best| time < best) => {
+                *best = Some(time);
+                self.changed = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether progress changed since the last call.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Restores saved progress; entries for maps no longer in the campaign are ignored.
+    pub fn apply_progress(&mut self, progress: &Progress) {
+        for (i, level) in self.levels.iter().enumerate() {
+            self.completed[i] = progress.completed_levels.contains(&level.map);
+            self.best_times[i] = progress
+                .best_times_ms
+                .get(&level.map)
+                .map(|ms| Duration::from_millis(*ms));
+        }
+    }
+
+    pub fn progress(&self) -> Progress {
+        let mut progress = Progress::default();
+        for (i, level) in self.levels.iter().enumerate() {
+            if self.is_completed(i) {
+                progress.completed_levels.push(level.map.clone());
+            }
+            if let Some(time) = self.best_time(i) {
+                progress
+                    .best_times_ms
+                    .insert(level.map.clone(), time.as_millis() as u64);
+            }
+        }
+        progress
+    }
+
+    pub fn duck_params(&self) -> Option<DuckParams> {
+        self.duck_params
+    }
+
+    /// Stops levels from loading and hot-reloading `duck.ron`.
+    pub fn pin_duck_params(&mut self, duck_params: DuckParams) {
+        self.duck_params = Some(duck_params);
+    }
+
+    pub fn next_level(&self, index: usize) -> Option<usize> {
+        Some(index + 1).filter(|next| *next < self.levels.len())
+    }
+}
+
+#[derive(Debug)]
+pub enum CampaignError {
+    Parse(DeRonErr),
+    NoLevels,
+}
+
+impl fmt::Display for CampaignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CampaignError::Parse(err) => write!(f, "can't parse campaign: {}", err),
+            CampaignError::NoLevels => write!(f, "campaign has no levels"),
+        }
+    }
+}
+
+impl std::error::Error for CampaignError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAMPAIGN_RON: &str = include_str!("../assets/campaign.ron");
+
+    #[test]
+    fn should_list_shipped_levels_in_order() {
+        let campaign = Campaign::from_ron(CAMPAIGN_RON).unwrap();
+        assert_eq!(campaign.levels()[0].map, "map.json");
+        assert!(campaign.levels().len() > 1);
+    }
+
+    #[test]
+    fn should_unlock_next_level_on_completion() {
+        let mut campaign = Campaign::from_ron(CAMPAIGN_RON).unwrap();
+        assert!(campaign.is_unlocked(0));
+        assert!(!campaign.is_unlocked(1));
+
+        campaign.complete(0);
+
+        assert!(campaign.is_completed(0));
+        assert!(campaign.is_unlocked(1));
+        assert_eq!(campaign.next_level(0), Some(1));
+        assert_eq!(campaign.next_level(campaign.levels().len() - 1), None);
+    }
+
+    #[test]
+    fn should_keep_only_best_time() {
+        let mut campaign = Campaign::from_ron(CAMPAIGN_RON).unwrap();
+        assert!(campaign.record_time(0, Duration::from_secs(20)));
+        assert!(!campaign.record_time(0, Duration::from_secs(25)));
+        assert!(campaign.record_time(0, Duration::from_secs(15)));
+        assert_eq!(campaign.best_time(0), Some(Duration::from_secs(15)));
+        assert!(campaign.take_changed());
+        assert!(!campaign.take_changed());
+    }
+
+    #[test]
+    fn should_restore_progress_by_map() {
+        let mut campaign = Campaign::from_ron(CAMPAIGN_RON).unwrap();
+        campaign.complete(0);
+        campaign.record_time(0, Duration::from_millis(12_345));
+        let progress = campaign.progress();
+
+        let mut restored = Campaign::from_ron(CAMPAIGN_RON).unwrap();
+        restored.apply_progress(&progress);
+
+        assert!(restored.is_completed(0));
+        assert!(!restored.is_completed(1));
+        assert_eq!(restored.best_time(0), Some(Duration::from_millis(12_345)));
+        assert!(!restored.take_changed());
+    }
+
+    #[test]
+    fn should_reject_empty_campaign() {
+        assert!(matches!(
+            Campaign::from_ron("(levels: [])"),
+            Err(CampaignError::NoLevels)
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+use macroquad::prelude::*;
+
+use crate::camera::CameraState;
+use crate::collectibles::Collectibles;
+use crate::tiled_map::TiledMap;
+
+/// Name of the Tiled rectangles that save the duck's progress when touched.
+pub const CHECKPOINT: &str = "checkpoint";
+
+const POLE_WIDTH: f32 = 4.0;
+const FLAG_WIDTH: f32 = 24.0;
+const FLAG_HEIGHT: f32 = 16.0;
+
+/// What a respawn brings back: where the duck stands and the level as it was then.
+#[derive(Clone)]
+pub struct CheckpointSnapshot {
+    pub duck_pos: Vec2,
+    pub collectibles: Collectibles,
+    pub camera: CameraState,
+}
+
+struct Checkpoint {
+    rect: Rect,
+    reached: bool,
+}
+
+/// The level's checkpoints; each one saves a snapshot only the first time it's touched.
+#[derive(Default)]
+pub struct Checkpoints {
+    items: Vec<Checkpoint>,
+}
+
+impl Checkpoints {
+    pub fn from_map(tiled_map: &TiledMap) -> Self {
+        let items = tiled_map
+            .objects()
+            .iter()
+            .filter(|object| object.name == CHECKPOINT)
+            .map(|object| Checkpoint {
+                rect: object.rect,
+                reached: false,
+            })
+            .collect();
+        Self { items }
+    }
+
+    /// Marks the first unreached checkpoint overlapping `rect` as reached and returns its bounds.
+    pub fn reach_overlapping(&mut self, rect: Rect) -> Option<Rect> {
+        let checkpoint = self
+            .items
+            .iter_mut()
+            .find(|checkpoint| !checkpoint.reached && checkpoint.rect.overlaps(&rect))?;
+        checkpoint.reached = true;
+        Some(checkpoint.rect)
+    }
+
+    pub fn reset(&mut self) {
+        for checkpoint in &mut self.items {
+            checkpoint.reached = false;
+        }
+    }
+
+    /// A pole at the left edge of each checkpoint, with a flag raised once it's reached.
+    pub fn draw(&self, viewport: Rect) {
+        for checkpoint in &self.items {
+            let rect = checkpoint.rect;
+            if !rect.overlaps(&viewport) {
+                continue;
+            }
+            draw_rectangle(rect.x, rect.y, POLE_WIDTH, rect.h, DARKBROWN);
+            let (flag_y, color) = if checkpoint.reached {
+                (rect.y, RED)
+            } else {
+                (rect.bottom() - FLAG_HEIGHT, LIGHTGRAY)
+            };
+            draw_rectangle(rect.x + POLE_WIDTH, flag_y, FLAG_WIDTH, FLAG_HEIGHT, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoints() -> Checkpoints {
+        let checkpoint = |x| Checkpoint {
+            rect: Rect::new(x, 0.0, 64.0, 96.0),
+            reached: false,
+        };
+        Checkpoints {
+            items: vec![checkpoint(0.0), checkpoint(200.0)],
+        }
+    }
+
+    #[test]
+    fn should_reach_each_checkpoint_once() {
+        let mut checkpoints = checkpoints();
+        let duck = Rect::new(210.0, 40.0, 32.0, 32.0);
+
+        assert_eq!(
+            checkpoints.reach_overlapping(duck),
+            Some(Rect::new(200.0, 0.0, 64.0, 96.0))
+        );
+        assert_eq!(checkpoints.reach_overlapping(duck), None);
+
+        checkpoints.reset();
+        assert!(checkpoints.reach_overlapping(duck).is_some());
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt;
+
+use macroquad::prelude::*;
+
+use crate::assets_server::AssetError;
+use crate::tiled_map::TiledMap;
+
+/// Object property that turns a Tiled object into a pickup: `coin`, `feather` or `bread`.
+pub const COLLECTIBLE_PROPERTY: &str = "collectible";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectibleKind {
+    Coin,
+    Feather,
+    Bread,
+}
+
+impl CollectibleKind {
+    pub const ALL: [CollectibleKind; 3] = [
+        CollectibleKind::Coin,
+        CollectibleKind::Feather,
+        CollectibleKind::Bread,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CollectibleKind::Coin => "coin",
+            CollectibleKind::Feather => "feather",
+            CollectibleKind::Bread => "bread",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            CollectibleKind::Coin => GOLD,
+            CollectibleKind::Feather => WHITE,
+            CollectibleKind::Bread => BEIGE,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Collectible {
+    kind: CollectibleKind,
+    rect: Rect,
+    collected: bool,
+}
+
+/// How many of one kind the player picked up out of how many the level has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tally {
+    pub kind: CollectibleKind,
+    pub collected: usize,
+    pub total: usize,
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}/{}", self.kind.name(), self.collected, self.total)
+    }
+}
+
+/// Pickups placed on the map, and which of them the duck already took.
+#[derive(Default, Clone)]
+pub struct Collectibles {
+    items: Vec<Collectible>,
+}
+
+impl Collectibles {
+    pub fn from_map(tiled_map: &TiledMap, map_path: &str) -> Result<Self, AssetError> {
+        let mut items = vec![];
+        for object in tiled_map.objects() {
+            let Some(value) = object.properties.get(COLLECTIBLE_PROPERTY) else {
+                continue;
+            };
+            let kind = value
+                .as_str()
+                .and_then(CollectibleKind::from_name)
+                .ok_or_else(|| AssetError::InvalidProperty {
+                    path: map_path.into(),
+                    cause: format!(
+                        "object \"{}\" has {}={}, expected coin, feather or bread",
+                        object.name, COLLECTIBLE_PROPERTY, value
+                    ),
+                })?;
+            items.push(Collectible {
+                kind,
+                rect: object.rect,
+                collected: false,
+            });
+        }
+        Ok(Self { items })
+    }
+
+    /// Takes every pickup overlapping `rect`; returns how many were taken.
+    pub fn collect_overlapping(&mut self, rect: Rect) -> usize {
+        let mut count = 0;
+        for item in self.items.iter_mut().filter(|item| !item.collected) {
+            if item.rect.overlaps(&rect) {
+                item.collected = true;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn reset(&mut self) {
+        for item in &mut self.items {
+            item.collected = false;
+        }
+    }
+
+    /// One tally per kind the level has, in `CollectibleKind::ALL` order.
+    pub fn tallies(&self) -> Vec<Tally> {
+        CollectibleKind::ALL
+            .into_iter()
+            .map(|kind| {
+                let of_kind = self.items.iter().filter(|item| item.kind == kind);
+                Tally {
+                    kind,
+                    collected: of_kind.clone().filter(|item| item.collected).count(),
+                    total: of_kind.count(),
+                }
+            })
+            .filter(|tally| tally.total > 0)
+            .collect()
+    }
+
+    pub fn draw(&self, viewport: Rect) {
+        for item in &self.items {
+            if !item.collected && item.rect.overlaps(&viewport) {
+                let radius = item.rect.w / 2.0;
+                let center = item.rect.point() + vec2(radius, item.rect.h / 2.0);
+                draw_circle(center.x, center.y, radius, item.kind.color());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILESET_JSON: &str = include_str!("../assets/tmw_desert_spacing.json");
+
+    fn parse(map_json: &str) -> TiledMap {
+        TiledMap::parse(
+            "map.json",
+            map_json,
+            &[("tmw_desert_spacing.json", TILESET_JSON)],
+            &[("tmw_desert_spacing.png", Texture2D::empty())],
+        )
+        .unwrap()
+    }
+
+    fn map_json(kinds: &[&str]) -> String {
+        let objects = kinds
+            .iter()
+            .enumerate()
+            .map(|(i, kind)| {
+                format!(
+                    r#"{{"name": "{kind}", "x": {x}, "y": 0, "width": 16, "height": 16,
+                        "properties": [{{"name": "collectible", "type": "string", "value": "{kind}"}}]}}"#,
+                    kind = kind,
+                    x = i * 32
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            r#"{{"width": 1, "height": 1, "tilewidth": 32, "tileheight": 32,
+                "layers": [{{"name": "Objects", "type": "objectgroup", "objects": [{}]}}],
+                "tilesets": [{{"firstgid": 1, "source": "tmw_desert_spacing.json"}}]}}"#,
+            objects
+        )
+    }
+
+    #[test]
+    fn should_collect_overlapping_items_once() {
+        let tiled_map = parse(&map_json(&["coin", "coin", "feather"]));
+        let mut collectibles = Collectibles::from_map(&tiled_map, "map.json").unwrap();
+        let duck = Rect::new(-8.0, 0.0, 48.0, 32.0);
+
+        assert_eq!(collectibles.collect_overlapping(duck), 2);
+        assert_eq!(collectibles.collect_overlapping(duck), 0);
+
+        let tallies = collectibles.tallies();
+        assert_eq!(tallies.len(), 2);
+        assert_eq!(tallies[0].to_string(), "coin 2/2");
+        assert_eq!(tallies[1].to_string(), "feather 0/1");
+    }
+
+    #[test]
+    fn should_put_items_back_on_reset() {
+        let tiled_map = parse(&map_json(&["bread"]));
+        let mut collectibles = Collectibles::from_map(&tiled_map, "map.json").unwrap();
+        collectibles.collect_overlapping(Rect::new(0.0, 0.0, 16.0, 16.0));
+
+        collectibles.reset();
+
+        assert_eq!(collectibles.tallies()[0].collected, 0);
+    }
+
+    #[test]
+    fn should_reject_unknown_kind() {
+        let tiled_map = parse(&map_json(&["gem"]));
+        let err = Collectibles::from_map(&tiled_map, "map.json")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("collectible=gem"));
+    }
+}
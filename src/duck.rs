@@ -3,16 +3,59 @@ use std::time::Duration;
 use macroquad::prelude::*;
 use macroquad_platformer::{Actor, Tile, World};
 
+use crate::animation::SpriteSheet;
+use crate::duck_animation::{DuckAnimation, DuckMotion};
+use crate::duck_params::DuckParams;
+use crate::input_service::{Action, InputService};
 use crate::physics::*;
+use crate::slopes::Slopes;
+use crate::surfaces::{Surface, Surfaces};
+use crate::water::Water;
 
-pub const MAX_JUMP_HEIGHT: Length = Length::from_meters(1.6);
-pub const HOVER_VELOCITY: Velocity = Velocity::from_meters_on_second(1.6);
-pub const GRAVITY_ACCELERATION: Acceleration =
-    Acceleration::from_meters_on_second_on_second(2.0 * EARTH_G.as_meters_on_second_on_second());
-pub const MAX_FALL_VELOCITY: Velocity = Velocity::from_meters_on_second(10.0);
-pub const MAX_MOVE_VELOCITY: Velocity = Velocity::from_meters_on_second(3.2);
-pub const MOVE_ACCELERATION: Acceleration = Acceleration::from_meters_on_second_on_second(12.0);
-pub const MOVE_DECELERATION: Acceleration = Acceleration::from_meters_on_second_on_second(6.0);
+/// How far up or down, in pixels, the duck's feet follow a slope in a single step.
+pub const SLOPE_SNAP: f32 = 16.0;
+pub const MAX_HEALTH: u32 = 3;
+/// How long after a hit the duck can't be hurt again.
+pub const INVULNERABILITY_TIME: Duration = Duration::from_millis(1500);
+/// How fast a hit sends the duck up and away from what hurt it.
+pub const KNOCKBACK_VELOCITY: XY<Velocity> = XY {
+    x: Velocity::from_meters_on_second(2.5),
+    y: Velocity::from_meters_on_second(3.0),
+};
+/// How long the duck stays hidden, then shown, while it blinks invulnerable.
+const BLINK_INTERVAL: Duration = Duration::from_millis(100);
+/// How far, in pixels, the dying duck floats up before it disappears.
+const DEATH_RISE_HEIGHT: f32 = 48.0;
+
+/// Snapshot of the controls the duck reacts to during a single update.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct DuckInput {
+    /// Horizontal movement in `[-1, 1]`; analog input scales the acceleration.
+    pub horizontal: f32,
+    pub down: bool,
+    pub jump: bool,
+    pub jump_pressed: bool,
+}
+
+impl From<&InputService> for DuckInput {
+    fn from(input: &InputService) -> Self {
+        Self {
+            horizontal: input.horizontal_axis(),
+            down: input.is_held(Action::Descend),
+            jump: input.is_held(Action::Jump),
+            jump_pressed: input.is_pressed(Action::Jump),
+        }
+    }
+}
+
+/// Moves a level can switch on; all of them are off by default.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuckAbilities {
+    /// Falling while pushing into a wall is capped at the wall slide velocity.
+    pub wall_slide: bool,
+    /// Jumping in the air next to a wall kicks off away from it.
+    pub wall_jump: bool,
+}
 
 #[derive(PartialEq)]
 enum HorizontalDirection {
@@ -21,81 +64,320 @@ enum HorizontalDirection {
 }
 
 pub struct Duck {
-    texture: Texture2D,
+    sheet: SpriteSheet,
+    animation: DuckAnimation,
+    size: Vec2,
     actor: Actor,
+    previous_pos: Vec2,
     velocity: XY<Velocity>,
     direction_h: HorizontalDirection,
+    params: DuckParams,
+    abilities: DuckAbilities,
+    water: Water,
+    surfaces: Surfaces,
+    slopes: Slopes,
+    coyote_time_left: Duration,
+    jump_buffer_left: Duration,
+    /// Whether the last step started a drop through a jump-through platform.
+    descending: bool,
+    /// Rising from a spring, which letting go of jump doesn't cut short.
+    launched: bool,
+    health: u32,
+    invulnerable_left: Duration,
 }
 
 impl Duck {
-    pub fn create(texture: Texture2D, world: &mut World, init_pos: Vec2) -> Self {
+    /// Creates a duck as big as a frame of its sprite sheet.
+    pub fn create(sheet: SpriteSheet, world: &mut World, init_pos: Vec2) -> Self {
+        let size = sheet.frame_size();
+        Self::with_size(sheet, size, world, init_pos)
+    }
+
+    /// Creates a duck without a texture, for simulations that never draw.
+    pub fn headless(world: &mut World, init_pos: Vec2, size: Vec2) -> Self {
+        let sheet = SpriteSheet::still(Texture2D::empty(), size);
+        Self::with_size(sheet, size, world, init_pos)
+    }
+
+    fn with_size(sheet: SpriteSheet, size: Vec2, world: &mut World, init_pos: Vec2) -> Self {
         Self {
-            texture,
-            actor: world.add_actor(init_pos, texture.width() as _, texture.height() as _),
+            sheet,
+            animation: DuckAnimation::default(),
+            size,
+            actor: world.add_actor(init_pos, size.x as _, size.y as _),
+            previous_pos: init_pos,
             velocity: Default::default(),
             direction_h: HorizontalDirection::Right,
+            params: DuckParams::default(),
+            abilities: DuckAbilities::default(),
+            water: Water::default(),
+            surfaces: Surfaces::default(),
+            slopes: Slopes::default(),
+            coyote_time_left: Duration::ZERO,
+            jump_buffer_left: Duration::ZERO,
+            descending: false,
+            launched: false,
+            health: MAX_HEALTH,
+            invulnerable_left: Duration::ZERO,
         }
     }
 
-    pub fn draw(&self, world: &World) {
-        let pos = self.pos(world);
-        let width = self.texture.width();
-        let height = self.texture.height();
+    /// Draws the duck `alpha` of the way from its previous to its current physics position,
+    /// blinking while it's invulnerable.
+    pub fn draw(&self, world: &World, alpha: f32) {
+        let blinks = self.invulnerable_left.as_nanos() / BLINK_INTERVAL.as_nanos();
+        if blinks % 2 == 1 {
+            return;
+        }
+        self.draw_at(self.interpolated_pos(world, alpha), WHITE, false);
+    }
+
+    /// Draws the duck upside down, floating up and fading out as `progress` goes from 0 to 1.
+    pub fn draw_dying(&self, world: &World, progress: f32) {
+        let pos = self.pos(world) - vec2(0.0, DEATH_RISE_HEIGHT * progress);
+        let color = Color::new(1.0, 1.0, 1.0, 1.0 - progress);
+        self.draw_at(pos, color, true);
+    }
+
+    fn draw_at(&self, pos: Vec2, color: Color, flip_y: bool) {
         draw_texture_ex(
-            self.texture,
+            self.sheet.texture(),
             pos.x,
             pos.y,
-            WHITE,
+            color,
             DrawTextureParams {
-                source: Some(Rect::new(0.0, 0.0, width as _, height as _)),
+                source: Some(self.sheet.source(self.animation.animation())),
                 flip_x: self.direction_h == HorizontalDirection::Left,
+                flip_y,
                 ..Default::default()
             },
         );
     }
 
+    /// Puts the duck back at `pos` at rest, as if it had just been created but for its health.
+    pub fn respawn(&mut self, world: &mut World, pos: Vec2) {
+        world.set_actor_position(self.actor, pos);
+        self.previous_pos = pos;
+        self.velocity = Default::default();
+        self.direction_h = HorizontalDirection::Right;
+        self.coyote_time_left = Duration::ZERO;
+        self.jump_buffer_left = Duration::ZERO;
+        self.descending = false;
+        self.launched = false;
+        self.invulnerable_left = Duration::ZERO;
+        self.animation = DuckAnimation::default();
+    }
+
+    pub fn health(&self) -> u32 {
+        self.health
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.health > 0
+    }
+
+    pub fn restore_health(&mut self) {
+        self.health = MAX_HEALTH;
+    }
+
+    /// Loses a point of health whether or not the duck is invulnerable, as when it hits a hazard.
+    pub fn lose_health(&mut self) {
+        self.health = self.health.saturating_sub(1);
+    }
+
+    /// Takes a hit unless still invulnerable from the last one: loses a point of health, gets
+    /// knocked up and towards `direction`, `1.0` right or `-1.0` left, and turns invulnerable.
+    /// Returns whether the hit landed.
+    pub fn hurt(&mut self, direction: f32) -> bool {
+        if !self.invulnerable_left.is_zero() {
+            return false;
+        }
+        self.lose_health();
+        self.velocity = XY {
+            x: direction * KNOCKBACK_VELOCITY.x,
+            y: -KNOCKBACK_VELOCITY.y,
+        };
+        self.coyote_time_left = Duration::ZERO;
+        self.invulnerable_left = INVULNERABILITY_TIME;
+        true
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerable_left.is_zero()
+    }
+
+    /// Sends the duck up at `velocity`, as when it lands on an enemy.
+    pub fn bounce(&mut self, velocity: Velocity) {
+        self.velocity.y = -velocity;
+        self.coyote_time_left = Duration::ZERO;
+    }
+
+    pub fn actor(&self) -> Actor {
+        self.actor
+    }
+
     pub fn pos(&self, world: &World) -> Vec2 {
         world.actor_pos(self.actor)
     }
 
-    pub fn center(&self, world: &World) -> Vec2 {
-        self.pos(world) + vec2(self.texture.width() / 2.0, 0.0)
+    /// The actor's current bounds in the physics world.
+    pub fn rect(&self, world: &World) -> Rect {
+        let pos = self.pos(world);
+        Rect::new(pos.x, pos.y, self.size.x, self.size.y)
+    }
+
+    pub fn interpolated_pos(&self, world: &World, alpha: f32) -> Vec2 {
+        self.previous_pos.lerp(self.pos(world), alpha)
+    }
+
+    pub fn center(&self, world: &World, alpha: f32) -> Vec2 {
+        self.interpolated_pos(world, alpha) + vec2(self.size.x / 2.0, 0.0)
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn velocity(&self) -> XY<Velocity> {
+        self.velocity
+    }
+
+    pub fn animation(&self) -> &DuckAnimation {
+        &self.animation
+    }
+
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    pub fn params(&self) -> &DuckParams {
+        &self.params
+    }
+
+    /// Takes effect from the next step; the current velocity is kept.
+    pub fn set_params(&mut self, params: DuckParams) {
+        self.params = params;
+    }
+
+    pub fn abilities(&self) -> DuckAbilities {
+        self.abilities
+    }
+
+    pub fn set_abilities(&mut self, abilities: DuckAbilities) {
+        self.abilities = abilities;
     }
 
-    pub fn update(&mut self, world: &mut World) {
-        DuckUpdateAction::new(self, world).apply();
+    /// Water the duck swims in instead of falling.
+    pub fn set_water(&mut self, water: Water) {
+        self.water = water;
+    }
+
+    /// What the ground tiles are made of, so ice, mud and springs change how the duck moves.
+    pub fn set_surfaces(&mut self, surfaces: Surfaces) {
+        self.surfaces = surfaces;
+    }
+
+    /// Sloped tiles the duck walks up and down instead of bumping into.
+    pub fn set_slopes(&mut self, slopes: Slopes) {
+        self.slopes = slopes;
+    }
+
+    /// Whether the duck stands on a block, a platform or a slope.
+    fn is_standing(&self, world: &World) -> bool {
+        world.collide_check(self.actor, self.pos(world) + vec2(0.0, 1.0))
+            || self.slopes.floor_under(self.rect(world), 1.0).is_some()
+    }
+
+    /// Advances the duck by `frame_time` using an explicit input snapshot.
+    pub fn step(&mut self, world: &mut World, input: DuckInput, frame_time: Duration) {
+        self.previous_pos = self.pos(world);
+        self.descending = false;
+        self.invulnerable_left = self.invulnerable_left.saturating_sub(frame_time);
+        DuckUpdateAction::new(self, world, input, frame_time).apply();
+
+        let is_on_ground = self.is_standing(world);
+        let motion = DuckMotion {
+            is_on_ground,
+            velocity: self.velocity,
+            is_hovering: input.jump && self.velocity.y > Velocity::ZERO,
+        };
+        self.animation.update(motion, &self.sheet, frame_time);
     }
 }
 
 struct DuckUpdateAction<'a> {
     duck: &'a mut Duck,
     world: &'a mut World,
+    input: DuckInput,
     frame_time: Duration,
     is_on_ground: bool,
+    /// How much of the duck is under water, from 0 to 1.
+    submersion: f32,
+    /// What the duck stands on; plain ground while it's in the air.
+    surface: Surface,
+    is_on_slope: bool,
+    /// Jump pressed this step or recently enough to still be buffered, until a jump uses it.
+    is_jump_requested: bool,
 }
 
 impl<'a> DuckUpdateAction<'a> {
-    fn new(duck: &'a mut Duck, world: &'a mut World) -> Self {
+    fn new(
+        duck: &'a mut Duck,
+        world: &'a mut World,
+        input: DuckInput,
+        frame_time: Duration,
+    ) -> Self {
         let mut duck_update_action = Self {
             duck,
             world,
-            frame_time: Duration::from_secs_f32(get_frame_time()),
+            input,
+            frame_time,
             is_on_ground: Default::default(),
+            submersion: Default::default(),
+            surface: Default::default(),
+            is_on_slope: Default::default(),
+            is_jump_requested: Default::default(),
         };
         duck_update_action.init();
         duck_update_action
     }
 
     fn init(&mut self) {
-        let pos = self.world.actor_pos(self.duck.actor) + vec2(0.0, 1.0);
-        self.is_on_ground = self.world.collide_check(self.duck.actor, pos)
+        self.is_on_ground = self.duck.is_standing(self.world);
+        let rect = self.duck.rect(self.world);
+        self.is_on_slope = self.duck.slopes.floor_under(rect, 1.0).is_some();
+        self.submersion = self.duck.water.submersion(rect);
+        if self.is_on_ground {
+            self.surface = self.duck.surfaces.under(rect);
+        }
+        self.update_jump_timers();
+    }
+
+    fn update_jump_timers(&mut self) {
+        let duck = &mut *self.duck;
+        duck.coyote_time_left = if self.is_on_ground && duck.velocity.y >= Velocity::ZERO {
+            duck.params.coyote_time
+        } else {
+            duck.coyote_time_left.saturating_sub(self.frame_time)
+        };
+        duck.jump_buffer_left = if self.input.jump_pressed {
+            duck.params.jump_buffer_time
+        } else {
+            duck.jump_buffer_left.saturating_sub(self.frame_time)
+        };
+        // The buffer only keeps a press for later steps, so a zero buffer still jumps on the press
+        self.is_jump_requested = self.input.jump_pressed || !duck.jump_buffer_left.is_zero();
     }
 
     pub fn apply(mut self) {
         self.handle_gravity();
+        self.handle_spring();
         self.handle_move();
         self.handle_jump();
+        self.handle_wall_slide();
+        self.handle_water_drag();
         self.update_position();
+        self.handle_slopes();
     }
 
     fn handle_gravity(&mut self) {
@@ -103,9 +385,31 @@ impl<'a> DuckUpdateAction<'a> {
             self.duck.velocity.y = Velocity::ZERO;
         } else if self.is_top_at_solid() {
             self.duck.velocity.y = -self.duck.velocity.y / 2.0;
+        } else if self.is_swimming() {
+            // Buoyancy cancels gravity once the duck is in as deep as it floats
+            let buoyancy = self.submersion / self.duck.params.float_submersion;
+            let dv = (1.0 - buoyancy) * self.duck.params.gravity_acceleration * self.frame_time;
+            let max_fall_velocity = self.duck.params.max_fall_velocity;
+            self.duck.velocity.y = (self.duck.velocity.y + dv).min(max_fall_velocity);
         } else {
-            let dv = GRAVITY_ACCELERATION * self.frame_time;
-            self.duck.velocity.y = (self.duck.velocity.y + dv).min(MAX_FALL_VELOCITY);
+            let dv = self.duck.params.gravity_acceleration * self.frame_time;
+            let max_fall_velocity = self.duck.params.max_fall_velocity;
+            self.duck.velocity.y = (self.duck.velocity.y + dv).min(max_fall_velocity);
+        }
+    }
+
+    fn handle_spring(&mut self) {
+        if self.duck.velocity.y >= Velocity::ZERO {
+            self.duck.launched = false;
+        }
+        let launch_velocity = self
+            .surface
+            .launch_velocity()
+            .filter(|_| self.is_on_ground());
+        if let Some(launch_velocity) = launch_velocity {
+            self.duck.velocity.y = -launch_velocity;
+            self.duck.launched = true;
+            self.duck.coyote_time_left = Duration::ZERO;
         }
     }
 
@@ -122,69 +426,173 @@ impl<'a> DuckUpdateAction<'a> {
         self.world.collide_check(self.duck.actor, pos) && self.is_solid_at(pos)
     }
 
+    /// Which side, `1.0` right or `-1.0` left, a solid wall touches the duck on.
+    fn wall_side(&self) -> Option<f32> {
+        let pos = self.world.actor_pos(self.duck.actor);
+        [1.0, -1.0].into_iter().find(|side| {
+            let beside = pos + vec2(*side, 0.0);
+            self.world.collide_check(self.duck.actor, beside) && self.is_solid_at(beside)
+        })
+    }
+
     fn is_solid_at(&self, pos: Vec2) -> bool {
-        let width = self.duck.texture.width();
-        let height = self.duck.texture.height();
-        self.world.collide_solids(pos, width as _, height as _) == Tile::Solid
+        let size = self.duck.size;
+        self.world.collide_solids(pos, size.x as _, size.y as _) == Tile::Solid
     }
 
     fn handle_move(&mut self) {
-        if is_key_down(KeyCode::Right) {
-            let dv = MOVE_ACCELERATION * self.frame_time;
-            self.duck.velocity.x = (self.duck.velocity.x + dv).min(MAX_MOVE_VELOCITY);
+        let params = self.duck.params;
+        let max_move_velocity = self.surface.speed() * params.max_move_velocity;
+        if self.input.horizontal > 0.0 {
+            let dv = self.input.horizontal * params.move_acceleration * self.frame_time;
+            self.duck.velocity.x = (self.duck.velocity.x + dv).min(max_move_velocity);
             self.duck.direction_h = HorizontalDirection::Right;
-        } else if is_key_down(KeyCode::Left) {
-            let dv = MOVE_ACCELERATION * self.frame_time;
-            self.duck.velocity.x = (self.duck.velocity.x - dv).max(-MAX_MOVE_VELOCITY);
+        } else if self.input.horizontal < 0.0 {
+            let dv = -self.input.horizontal * params.move_acceleration * self.frame_time;
+            self.duck.velocity.x = (self.duck.velocity.x - dv).max(-max_move_velocity);
             self.duck.direction_h = HorizontalDirection::Left;
         } else {
-            let dv = MOVE_DECELERATION * self.frame_time;
+            let dv = self.surface.grip() * params.move_deceleration * self.frame_time;
             self.duck.velocity.x = self.duck.velocity.x.signum()
                 * (self.duck.velocity.x.abs() - dv).max(Velocity::ZERO);
         }
     }
 
     fn handle_jump(&mut self) {
+        let params = self.duck.params;
         if self.is_descent() {
             self.world.descent(self.duck.actor);
-            self.duck.velocity.y = 2.0 * GRAVITY_ACCELERATION * self.frame_time;
+            self.duck.descending = true;
+            self.duck.velocity.y = 2.0 * params.gravity_acceleration * self.frame_time;
+            self.consume_jump();
         } else if self.is_jump_start() {
-            self.duck.velocity.y = -jump_velocity();
+            self.duck.velocity.y = -params.jump_velocity();
+            self.consume_jump();
+        } else if self.is_swimming() && self.is_jump_requested() {
+            // With its head above water the duck leaps out; further down it paddles up
+            self.duck.velocity.y = if self.submersion < 1.0 {
+                -params.jump_velocity()
+            } else {
+                self.duck.velocity.y.min(-params.paddle_velocity)
+            };
+            self.consume_jump();
+        } else if let Some(side) = self.wall_jump_side() {
+            self.duck.velocity.x = -side * params.wall_jump_push;
+            self.duck.velocity.y = -params.jump_velocity();
+            self.duck.direction_h = if side > 0.0 {
+                HorizontalDirection::Left
+            } else {
+                HorizontalDirection::Right
+            };
+            self.consume_jump();
         } else if self.is_jump_end() {
-            self.duck.velocity.y += GRAVITY_ACCELERATION * self.frame_time;
+            self.duck.velocity.y += params.gravity_acceleration * self.frame_time;
         } else if self.is_hover() {
-            self.duck.velocity.y = self.duck.velocity.y.min(HOVER_VELOCITY);
+            self.duck.velocity.y = self.duck.velocity.y.min(params.hover_velocity);
         }
     }
 
     fn is_descent(&self) -> bool {
-        is_key_down(KeyCode::Down) && is_key_pressed(KeyCode::Space) && self.is_on_ground()
+        self.input.down && self.is_jump_requested() && self.is_on_ground()
+    }
+
+    /// The side of the wall to kick off from, if the duck may wall jump now.
+    fn wall_jump_side(&self) -> Option<f32> {
+        if !self.duck.abilities.wall_jump || !self.is_jump_requested() || self.is_on_ground() {
+            return None;
+        }
+        self.wall_side()
+    }
+
+    fn handle_wall_slide(&mut self) {
+        if !self.duck.abilities.wall_slide || self.duck.velocity.y <= Velocity::ZERO {
+            return;
+        }
+        let is_pushing_into_wall = self
+            .wall_side()
+            .is_some_and(|side| side * self.input.horizontal > 0.0);
+        if is_pushing_into_wall {
+            let max_velocity = self.duck.params.wall_slide_velocity;
+            self.duck.velocity.y = self.duck.velocity.y.min(max_velocity);
+        }
     }
 
     fn is_jump_start(&self) -> bool {
-        is_key_pressed(KeyCode::Space) && self.is_on_ground()
+        self.is_jump_requested() && (self.is_on_ground() || self.is_in_coyote_time())
+    }
+
+    fn is_jump_requested(&self) -> bool {
+        self.is_jump_requested
+    }
+
+    fn is_in_coyote_time(&self) -> bool {
+        !self.duck.coyote_time_left.is_zero()
+    }
+
+    fn consume_jump(&mut self) {
+        self.duck.coyote_time_left = Duration::ZERO;
+        self.duck.jump_buffer_left = Duration::ZERO;
+        self.is_jump_requested = false;
     }
 
     fn is_jump_end(&self) -> bool {
-        !is_key_down(KeyCode::Space) && self.duck.velocity.y < Velocity::ZERO
+        !self.input.jump
+            && self.duck.velocity.y < Velocity::ZERO
+            && !self.is_swimming()
+            && !self.duck.launched
     }
 
     fn is_hover(&self) -> bool {
-        is_key_down(KeyCode::Space) && self.duck.velocity.y > Velocity::ZERO
+        self.input.jump && self.duck.velocity.y > Velocity::ZERO && !self.is_swimming()
+    }
+
+    fn is_swimming(&self) -> bool {
+        self.submersion > 0.0
+    }
+
+    fn handle_water_drag(&mut self) {
+        let params = self.duck.params;
+        let depth = (self.submersion / params.float_submersion).min(1.0);
+        // Compounded over the step, so the speed lost in a second doesn't depend on the timestep
+        let keep = (1.0 - params.water_drag).powf(depth * self.frame_time.as_secs_f32());
+        self.duck.velocity.x = keep * self.duck.velocity.x;
+        self.duck.velocity.y = keep * self.duck.velocity.y;
     }
 
+    /// Lifts the duck out of a slope it walked or fell into and, while it walks, keeps its feet
+    /// on a slope going down, so it doesn't fall off every step downhill.
+    fn handle_slopes(&mut self) {
+        if self.is_moving_up() {
+            return;
+        }
+        let rect = self.duck.rect(self.world);
+        match self.duck.slopes.floor_under(rect, SLOPE_SNAP) {
+            Some(floor) => {
+                let dy = (floor - rect.bottom()).round();
+                if dy < 0.0 || self.is_on_ground {
+                    self.world.move_v(self.duck.actor, dy);
+                }
+            }
+            // Stepping off the foot of a slope leaves the duck a few pixels above the block there
+            None if self.is_on_slope => {
+                let below = self.world.actor_pos(self.duck.actor) + vec2(0.0, SLOPE_SNAP);
+                if self.world.collide_check(self.duck.actor, below) {
+                    self.world.move_v(self.duck.actor, SLOPE_SNAP);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Semi-implicit Euler on purpose: by now the handlers above have set this step's velocity,
+    /// capping it for falls, wall slides, hovering and drag, so there is no constant acceleration
+    /// over the step for `displacement` to integrate.
     fn update_position(&mut self) {
         let dx = self.duck.velocity.x * self.frame_time;
         let dy = self.duck.velocity.y * self.frame_time;
-        self.world.move_h(self.duck.actor, dx.as_cm());
-        self.world.move_v(self.duck.actor, dy.as_cm());
+        self.world
+            .move_h(self.duck.actor, WORLD_SCALE.to_pixels(dx));
+        self.world
+            .move_v(self.duck.actor, WORLD_SCALE.to_pixels(dy));
     }
 }
-
-// mv^2/2 = mgh
-// v = sqrt(2gh)
-fn jump_velocity() -> Velocity {
-    let g = GRAVITY_ACCELERATION.as_meters_on_second_on_second();
-    let h = MAX_JUMP_HEIGHT.as_meters();
-    Velocity::from_meters_on_second((2.0 * g * h).sqrt())
-}
@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use crate::animation::{Animation, SpriteSheet};
+use crate::physics::{Velocity, XY};
+
+/// Clips the duck's sprite sheet must have.
+pub const DUCK_CLIPS: [&str; 6] = [
+    DuckPose::Idle.clip(),
+    DuckPose::Walk.clip(),
+    DuckPose::Jump.clip(),
+    DuckPose::Hover.clip(),
+    DuckPose::Fall.clip(),
+    DuckPose::Land.clip(),
+];
+/// Slower than this on the ground and the duck counts as standing still.
+const WALK_THRESHOLD: Velocity = Velocity::from_meters_on_second(0.1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuckPose {
+    Idle,
+    Walk,
+    Jump,
+    Hover,
+    Fall,
+    /// Plays once on touching down, then gives way to idle or walk.
+    Land,
+}
+
+impl DuckPose {
+    pub const fn clip(self) -> &'static str {
+        match self {
+            DuckPose::Idle => "idle",
+            DuckPose::Walk => "walk",
+            DuckPose::Jump => "jump",
+            DuckPose::Hover => "hover",
+            DuckPose::Fall => "fall",
+            DuckPose::Land => "land",
+        }
+    }
+
+    fn is_airborne(self) -> bool {
+        matches!(self, DuckPose::Jump | DuckPose::Hover | DuckPose::Fall)
+    }
+}
+
+/// What the duck was doing at the end of a step, as far as its animation cares.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuckMotion {
+    pub is_on_ground: bool,
+    pub velocity: XY<Velocity>,
+    /// Holding jump on the way down.
+    pub is_hovering: bool,
+}
+
+/// Picks the duck's clip from how it moves and plays it on game time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuckAnimation {
+    pose: DuckPose,
+    animation: Animation,
+}
+
+impl Default for DuckAnimation {
+    fn default() -> Self {
+        Self {
+            pose: DuckPose::Idle,
+            animation: Animation::new(DuckPose::Idle.clip()),
+        }
+    }
+}
+
+impl DuckAnimation {
+    pub fn pose(&self) -> DuckPose {
+        self.pose
+    }
+
+    pub fn animation(&self) -> &Animation {
+        &self.animation
+    }
+
+    pub fn update(&mut self, motion: DuckMotion, sheet: &SpriteSheet, frame_time: Duration) {
+        self.animation.advance(frame_time);
+        self.pose = self.next_pose(motion, sheet.is_finished(&self.animation));
+        self.animation.play(self.pose.clip());
+    }
+
+    fn next_pose(&self, motion: DuckMotion, is_clip_finished: bool) -> DuckPose {
+        if !motion.is_on_ground {
+            return if motion.velocity.y < Velocity::ZERO {
+                DuckPose::Jump
+            } else if motion.is_hovering {
+                DuckPose::Hover
+            } else {
+                DuckPose::Fall
+            };
+        }
+        if self.pose.is_airborne() || self.pose == DuckPose::Land && !is_clip_finished {
+            DuckPose::Land
+        } else if motion.velocity.x.abs() > WALK_THRESHOLD {
+            DuckPose::Walk
+        } else {
+            DuckPose::Idle
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Texture2D;
+
+    use super::*;
+
+    const DUCK_JSON: &str = include_str!("../assets/duck.json");
+
+    fn motion(is_on_ground: bool, x: f32, y: f32, is_hovering: bool) -> DuckMotion {
+        DuckMotion {
+            is_on_ground,
+            velocity: XY {
+                x: Velocity::from_meters_on_second(x),
+                y: Velocity::from_meters_on_second(y),
+            },
+            is_hovering,
+        }
+    }
+
+    #[test]
+    fn should_have_every_clip_in_shipped_sheet() {
+        let sheet = SpriteSheet::from_json(DUCK_JSON, Texture2D::empty()).unwrap();
+        sheet.require_clips(&DUCK_CLIPS).unwrap();
+    }
+
+    #[test]
+    fn should_follow_duck_through_a_jump() {
+        let sheet = SpriteSheet::from_json(DUCK_JSON, Texture2D::empty()).unwrap();
+        let step = Duration::from_millis(10);
+        let mut animation = DuckAnimation::default();
+        let mut pose = |motion| {
+            animation.update(motion, &sheet, step);
+            animation.pose()
+        };
+
+        assert_eq!(pose(motion(true, 0.0, 0.0, false)), DuckPose::Idle);
+        assert_eq!(pose(motion(true, 2.0, 0.0, false)), DuckPose::Walk);
+        assert_eq!(pose(motion(false, 2.0, -3.0, false)), DuckPose::Jump);
+        assert_eq!(pose(motion(false, 2.0, 1.0, true)), DuckPose::Hover);
+        assert_eq!(pose(motion(false, 2.0, 3.0, false)), DuckPose::Fall);
+        assert_eq!(pose(motion(true, 0.0, 0.0, false)), DuckPose::Land);
+        assert_eq!(pose(motion(true, 0.0, 0.0, false)), DuckPose::Land);
+        for _ in 0..20 {
+            pose(motion(true, 0.0, 0.0, false));
+        }
+        assert_eq!(pose(motion(true, 0.0, 0.0, false)), DuckPose::Idle);
+    }
+
+    #[test]
+    fn should_keep_frame_timing_while_pose_holds() {
+        let sheet = SpriteSheet::from_json(DUCK_JSON, Texture2D::empty()).unwrap();
+        let mut animation = DuckAnimation::default();
+        let walking = motion(true, 2.0, 0.0, false);
+        animation.update(walking, &sheet, Duration::ZERO);
+        let first = sheet.source(animation.animation());
+        animation.update(walking, &sheet, Duration::from_millis(100));
+        assert_ne!(sheet.source(animation.animation()), first);
+    }
+}
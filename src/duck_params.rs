@@ -0,0 +1,252 @@
+use std::fmt;
+use std::time::Duration;
+
+use nanoserde::{DeRon, DeRonErr, SerRon};
+
+use crate::physics::*;
+
+/// Tuning for the duck's movement, loaded from `duck.ron` so it can change without a rebuild.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DuckParams {
+    pub max_jump_height: Length,
+    pub hover_velocity: Velocity,
+    pub gravity_acceleration: Acceleration,
+    pub max_fall_velocity: Velocity,
+    pub max_move_velocity: Velocity,
+    pub move_acceleration: Acceleration,
+    pub move_deceleration: Acceleration,
+    /// How long after walking off a ledge a jump still counts as from the ground.
+    pub coyote_time: Duration,
+    /// How long before landing a jump press is remembered.
+    pub jump_buffer_time: Duration,
+    pub wall_slide_velocity: Velocity,
+    pub wall_jump_push: Velocity,
+    /// How much of the duck's height sits under the waterline when it floats.
+    pub float_submersion: f32,
+    /// Share of its velocity the duck loses over a second in water up to the waterline.
+    pub water_drag: f32,
+    /// How fast a paddle stroke sends the duck up while it's fully under water.
+    pub paddle_velocity: Velocity,
+}
+
+impl Default for DuckParams {
+    fn default() -> Self {
+        Self {
+            max_jump_height: Length::from_meters(1.6),
+            hover_velocity: Velocity::from_meters_on_second(1.6),
+            gravity_acceleration: 2.0 * EARTH_G,
+            max_fall_velocity: Velocity::from_meters_on_second(10.0),
+            max_move_velocity: Velocity::from_meters_on_second(3.2),
+            move_acceleration: Acceleration::from_meters_on_second_on_second(12.0),
+            move_deceleration: Acceleration::from_meters_on_second_on_second(6.0),
+            coyote_time: Duration::from_millis(100),
+            jump_buffer_time: Duration::from_millis(100),
+            wall_slide_velocity: Velocity::from_meters_on_second(1.2),
+            wall_jump_push: Velocity::from_meters_on_second(3.2),
+            float_submersion: 0.5,
+            water_drag: 0.95,
+            paddle_velocity: Velocity::from_meters_on_second(2.0),
+        }
+    }
+}
+
+/// On-disk layout: SI units (m, m/s, m/s²), except timing windows in milliseconds.
+#[derive(DeRon, SerRon)]
+struct DuckParamsFile {
+    max_jump_height: f32,
+    hover_velocity: f32,
+    gravity_acceleration: f32,
+    max_fall_velocity: f32,
+    max_move_velocity: f32,
+    move_acceleration: f32,
+    move_deceleration: f32,
+    coyote_time_ms: u64,
+    jump_buffer_time_ms: u64,
+    wall_slide_velocity: f32,
+    wall_jump_push: f32,
+    float_submersion: f32,
+    water_drag: f32,
+    paddle_velocity: f32,
+}
+
+impl DuckParams {
+    pub fn from_ron(ron: &str) -> Result<Self, DuckParamsError> {
+        let file = DuckParamsFile::deserialize_ron(ron).map_err(DuckParamsError::Parse)?;
+
+        positive("max_jump_height", file.max_jump_height)?;
+        positive("hover_velocity", file.hover_velocity)?;
+        positive("gravity_acceleration", file.gravity_acceleration)?;
+        positive("max_fall_velocity", file.max_fall_velocity)?;
+        positive("max_move_velocity", file.max_move_velocity)?;
+        positive("move_acceleration", file.move_acceleration)?;
+        non_negative("move_deceleration", file.move_deceleration)?;
+        positive("wall_slide_velocity", file.wall_slide_velocity)?;
+        non_negative("wall_jump_push", file.wall_jump_push)?;
+        positive("float_submersion", file.float_submersion)?;
+        at_most_one("float_submersion", file.float_submersion)?;
+        non_negative("water_drag", file.water_drag)?;
+        at_most_one("water_drag", file.water_drag)?;
+        positive("paddle_velocity", file.paddle_velocity)?;
+
+        Ok(Self {
+            max_jump_height: Length::from_meters(file.max_jump_height),
+            hover_velocity: Velocity::from_meters_on_second(file.hover_velocity),
+            gravity_acceleration: Acceleration::from_meters_on_second_on_second(
+                file.gravity_acceleration,
+            ),
+            max_fall_velocity: Velocity::from_meters_on_second(file.max_fall_velocity),
+            max_move_velocity: Velocity::from_meters_on_second(file.max_move_velocity),
+            move_acceleration: Acceleration::from_meters_on_second_on_second(
+                file.move_acceleration,
+            ),
+            move_deceleration: Acceleration::from_meters_on_second_on_second(
+                file.move_deceleration,
+            ),
+            coyote_time: Duration::from_millis(file.coyote_time_ms),
+            jump_buffer_time: Duration::from_millis(file.jump_buffer_time_ms),
+            wall_slide_velocity: Velocity::from_meters_on_second(file.wall_slide_velocity),
+            wall_jump_push: Velocity::from_meters_on_second(file.wall_jump_push),
+            float_submersion: file.float_submersion,
+            water_drag: file.water_drag,
+            paddle_velocity: Velocity::from_meters_on_second(file.paddle_velocity),
+        })
+    }
+
+    /// The same layout `from_ron` reads; timing windows are kept to whole milliseconds.
+    pub fn to_ron(&self) -> String {
+        DuckParamsFile {
+            max_jump_height: self.max_jump_height.as_meters(),
+            hover_velocity: self.hover_velocity.as_meters_on_second(),
+            gravity_acceleration: self.gravity_acceleration.as_meters_on_second_on_second(),
+            max_fall_velocity: self.max_fall_velocity.as_meters_on_second(),
+            max_move_velocity: self.max_move_velocity.as_meters_on_second(),
+            move_acceleration: self.move_acceleration.as_meters_on_second_on_second(),
+            move_deceleration: self.move_deceleration.as_meters_on_second_on_second(),
+            coyote_time_ms: self.coyote_time.as_millis() as u64,
+            jump_buffer_time_ms: self.jump_buffer_time.as_millis() as u64,
+            wall_slide_velocity: self.wall_slide_velocity.as_meters_on_second(),
+            wall_jump_push: self.wall_jump_push.as_meters_on_second(),
+            float_submersion: self.float_submersion,
+            water_drag: self.water_drag,
+            paddle_velocity: self.paddle_velocity.as_meters_on_second(),
+        }
+        .serialize_ron()
+    }
+
+    // mv^2/2 = mgh
+    // v = sqrt(2gh)
+    pub fn jump_velocity(&self) -> Velocity {
+        let g = self.gravity_acceleration.as_meters_on_second_on_second();
+        let h = self.max_jump_height.as_meters();
+        Velocity::from_meters_on_second((2.0 * g * h).sqrt())
+    }
+}
+
+fn positive(field: &'static str, value: f32) -> Result<(), DuckParamsError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(DuckParamsError::NotPositive { field, value })
+    }
+}
+
+fn non_negative(field: &'static str, value: f32) -> Result<(), DuckParamsError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(DuckParamsError::Negative { field, value })
+    }
+}
+
+/// For shares, which are already checked not to be negative.
+fn at_most_one(field: &'static str, value: f32) -> Result<(), DuckParamsError> {
+    if value <= 1.0 {
+        Ok(())
+    } else {
+        Err(DuckParamsError::AboveOne { field, value })
+    }
+}
+
+#[derive(Debug)]
+pub enum DuckParamsError {
+    Parse(DeRonErr),
+    NotPositive { field: &'static str, value: f32 },
+    Negative { field: &'static str, value: f32 },
+    AboveOne { field: &'static str, value: f32 },
+}
+
+impl fmt::Display for DuckParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuckParamsError::Parse(err) => write!(f, "can't parse duck params: {}", err),
+            DuckParamsError::NotPositive { field, value } => {
+                write!(f, "{} must be positive, got {}", field, value)
+            }
+            DuckParamsError::Negative { field, value } => {
+                write!(f, "{} can't be negative, got {}", field, value)
+            }
+            DuckParamsError::AboveOne { field, value } => {
+                write!(f, "{} can't be more than 1, got {}", field, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DuckParamsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT_RON: &str = include_str!("../assets/duck.ron");
+
+    #[test]
+    fn should_match_defaults_in_shipped_file() {
+        assert_eq!(
+            DuckParams::from_ron(DEFAULT_RON).unwrap(),
+            DuckParams::default()
+        );
+    }
+
+    #[test]
+    fn should_read_back_what_it_writes() {
+        let params = DuckParams {
+            hover_velocity: Velocity::from_meters_on_second(0.3),
+            coyote_time: Duration::from_millis(45),
+            ..Default::default()
+        };
+        assert_eq!(DuckParams::from_ron(&params.to_ron()).unwrap(), params);
+    }
+
+    #[test]
+    fn should_reject_negative_gravity() {
+        let ron = DEFAULT_RON.replace("gravity_acceleration: 19.6", "gravity_acceleration: -9.8");
+        let err = DuckParams::from_ron(&ron).unwrap_err();
+        assert!(matches!(
+            err,
+            DuckParamsError::NotPositive {
+                field: "gravity_acceleration",
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "gravity_acceleration must be positive, got -9.8"
+        );
+    }
+
+    #[test]
+    fn should_reject_drag_above_one() {
+        let ron = DEFAULT_RON.replace("water_drag: 0.95", "water_drag: 3.0");
+        let err = DuckParams::from_ron(&ron).unwrap_err();
+        assert_eq!(err.to_string(), "water_drag can't be more than 1, got 3");
+    }
+
+    #[test]
+    fn should_reject_missing_fields() {
+        assert!(matches!(
+            DuckParams::from_ron("(max_jump_height: 1.6)"),
+            Err(DuckParamsError::Parse(_))
+        ));
+    }
+}
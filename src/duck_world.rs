@@ -1,8 +1,7 @@
-use std::rc::Rc;
-
 use macroquad::prelude::Rect;
 use macroquad_platformer::{Tile, World};
 
+use crate::slopes::SLOPE_PROPERTY;
 use crate::tiled_map::TiledMap;
 
 pub struct DuckWorld {
@@ -36,24 +35,20 @@ impl DuckWorld {
         self.map.draw_all_layers_at_viewport(viewport);
     }
 
+    /// Makes every tile of `layer` a `collider_type` block, except sloped tiles, which the duck
+    /// walks along on its own.
     pub fn add_static_colliders(&mut self, layer: &str, collider_type: Tile) {
         let static_colliders = self
             .map
-            .tiles(layer, None)
-            .map(|(_, _, tile)| tile.as_ref().map_or(Tile::Empty, |_| collider_type))
+            .layer_tiles(layer)
+            .map(|(_, _, tile)| {
+                tile.filter(|tile| self.map.tile_property(tile, SLOPE_PROPERTY).is_none())
+                    .map_or(Tile::Empty, |_| collider_type)
+            })
             .collect::<Vec<_>>();
 
-        let map = &self.map.raw_tiled_map;
-        let tile_width = map.tilewidth;
-        let tile_height = map.tileheight;
-        let width = map.width;
-
-        self.add_static_tiled_layer(
-            static_colliders,
-            tile_width as _,
-            tile_height as _,
-            width as _,
-            1,
-        );
+        let grid = self.map.grid();
+        let tile_size = grid.tile_size();
+        self.add_static_tiled_layer(static_colliders, tile_size.x, tile_size.y, grid.width(), 1);
     }
 }
@@ -0,0 +1,472 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use macroquad::prelude::*;
+use macroquad_platformer::{Actor, Tile, World};
+
+use crate::assets_server::AssetError;
+use crate::duck_params::DuckParams;
+use crate::physics::*;
+use crate::tiled_map::{MapObject, TiledMap};
+
+/// Object property that turns a Tiled rectangle into an enemy: `walker`, `frog` or `flyer`.
+pub const ENEMY_PROPERTY: &str = "enemy";
+
+pub const WALKER_VELOCITY: Velocity = Velocity::from_meters_on_second(1.0);
+/// How long a frog sits on the ground between hops.
+pub const FROG_HOP_INTERVAL: Duration = Duration::from_millis(1200);
+pub const FROG_HOP_VELOCITY: XY<Velocity> = XY {
+    x: Velocity::from_meters_on_second(1.5),
+    y: Velocity::from_meters_on_second(4.0),
+};
+pub const FLYER_VELOCITY: Velocity = Velocity::from_meters_on_second(1.2);
+/// How far either side of its spawn a flyer patrols.
+pub const FLYER_RANGE: Length = Length::from_meters(2.0);
+/// How far a flyer bobs above and below its spawn height.
+pub const FLYER_AMPLITUDE: Length = Length::from_meters(0.4);
+pub const FLYER_PERIOD: Duration = Duration::from_secs(2);
+/// How fast the duck leaves an enemy it stomped on.
+pub const STOMP_BOUNCE_VELOCITY: Velocity = Velocity::from_meters_on_second(4.0);
+/// How deep, in pixels, the duck's feet may sink into an enemy and still count as landing on it.
+const STOMP_DEPTH: f32 = 16.0;
+const EYE_SIZE: f32 = 6.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyKind {
+    /// Patrols the ground, turning around at walls and ledges.
+    Walker,
+    /// Hops back and forth, waiting on the ground between hops.
+    Frog,
+    /// Ignores gravity, patrolling sideways while bobbing up and down.
+    Flyer,
+}
+
+impl EnemyKind {
+    pub const ALL: [EnemyKind; 3] = [EnemyKind::Walker, EnemyKind::Frog, EnemyKind::Flyer];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EnemyKind::Walker => "walker",
+            EnemyKind::Frog => "frog",
+            EnemyKind::Flyer => "flyer",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            EnemyKind::Walker => MAROON,
+            EnemyKind::Frog => DARKGREEN,
+            EnemyKind::Flyer => VIOLET,
+        }
+    }
+}
+
+/// An enemy as placed in the map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnemySpec {
+    pub kind: EnemyKind,
+    pub rect: Rect,
+}
+
+impl EnemySpec {
+    pub fn from_map(tiled_map: &TiledMap, map_path: &str) -> Result<Vec<Self>, AssetError> {
+        tiled_map
+            .objects()
+            .iter()
+            .filter_map(|object| {
+                let value = object.properties.get(ENEMY_PROPERTY)?;
+                Some(Self::from_object(object, value.as_str(), map_path))
+            })
+            .collect()
+    }
+
+    fn from_object(
+        object: &MapObject,
+        kind: Option<&str>,
+        map_path: &str,
+    ) -> Result<Self, AssetError> {
+        let invalid = |cause: String| AssetError::InvalidProperty {
+            path: map_path.into(),
+            cause: format!(
+                "enemy \"{}\" at {}: {}",
+                object.name,
+                object.rect.point(),
+                cause
+            ),
+        };
+        let kind = kind
+            .and_then(EnemyKind::from_name)
+            .ok_or_else(|| invalid(format!("{} must be walker, frog or flyer", ENEMY_PROPERTY)))?;
+        if object.rect.w < 1.0 || object.rect.h < 1.0 {
+            return Err(invalid("needs a rectangle to size it".into()));
+        }
+        Ok(Self {
+            kind,
+            rect: object.rect,
+        })
+    }
+}
+
+/// Per-kind state of an enemy's AI.
+enum Behaviour {
+    Walker,
+    Frog { hop_in: Duration },
+    Flyer { time: Duration },
+}
+
+impl Behaviour {
+    fn new(kind: EnemyKind) -> Self {
+        match kind {
+            EnemyKind::Walker => Behaviour::Walker,
+            EnemyKind::Frog => Behaviour::Frog {
+                hop_in: FROG_HOP_INTERVAL,
+            },
+            EnemyKind::Flyer => Behaviour::Flyer {
+                time: Duration::ZERO,
+            },
+        }
+    }
+}
+
+/// What touching an enemy did to the duck.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Contact {
+    /// The duck landed on top and squashed it.
+    Stomp,
+    /// The duck ran or jumped into it and gets knocked towards `direction`, `1.0` right or `-1.0`
+    /// left, away from the enemy.
+    Hurt { direction: f32 },
+}
+
+struct Enemy {
+    spec: EnemySpec,
+    actor: Actor,
+    previous_pos: Vec2,
+    velocity: XY<Velocity>,
+    /// `1.0` when heading right, `-1.0` when heading left.
+    direction: f32,
+    behaviour: Behaviour,
+    alive: bool,
+}
+
+impl Enemy {
+    fn new(world: &mut World, spec: EnemySpec) -> Self {
+        let pos = spec.rect.point();
+        Self {
+            spec,
+            actor: world.add_actor(pos, spec.rect.w as _, spec.rect.h as _),
+            previous_pos: pos,
+            velocity: Default::default(),
+            direction: -1.0,
+            behaviour: Behaviour::new(spec.kind),
+            alive: true,
+        }
+    }
+
+    fn reset(&mut self, world: &mut World) {
+        let pos = self.spec.rect.point();
+        world.set_actor_position(self.actor, pos);
+        self.previous_pos = pos;
+        self.velocity = Default::default();
+        self.direction = -1.0;
+        self.behaviour = Behaviour::new(self.spec.kind);
+        self.alive = true;
+    }
+
+    fn rect(&self, world: &World) -> Rect {
+        let pos = world.actor_pos(self.actor);
+        Rect::new(pos.x, pos.y, self.spec.rect.w, self.spec.rect.h)
+    }
+
+    fn step(&mut self, world: &mut World, frame_time: Duration) {
+        self.previous_pos = world.actor_pos(self.actor);
+        if !self.alive {
+            return;
+        }
+        let is_on_ground = world.collide_check(self.actor, self.previous_pos + vec2(0.0, 1.0));
+        match &mut self.behaviour {
+            Behaviour::Walker => {
+                if is_on_ground && !self.is_ground_ahead(world) {
+                    self.direction = -self.direction;
+                }
+                self.velocity.x = self.direction * WALKER_VELOCITY;
+            }
+            Behaviour::Frog { hop_in } => {
+                if is_on_ground && self.velocity.y >= Velocity::ZERO {
+                    self.velocity.x = Velocity::ZERO;
+                    *hop_in = hop_in.saturating_sub(frame_time);
+                    if hop_in.is_zero() {
+                        *hop_in = FROG_HOP_INTERVAL;
+                        self.velocity.x = self.direction * FROG_HOP_VELOCITY.x;
+                        self.velocity.y = -FROG_HOP_VELOCITY.y;
+                        self.direction = -self.direction;
+                    }
+                }
+            }
+            Behaviour::Flyer { time } => {
+                *time += frame_time;
+                let offset = self.previous_pos.x - self.spec.rect.x;
+                if offset * self.direction >= WORLD_SCALE.to_pixels(FLYER_RANGE) {
+                    self.direction = -self.direction;
+                }
+                self.velocity.x = self.direction * FLYER_VELOCITY;
+                let phase = TAU * time.as_secs_f32() / FLYER_PERIOD.as_secs_f32();
+                let target_y =
+                    self.spec.rect.y + WORLD_SCALE.to_pixels(FLYER_AMPLITUDE) * phase.sin();
+                world.move_v(self.actor, target_y - self.previous_pos.y);
+            }
+        }
+
+        if !matches!(self.behaviour, Behaviour::Flyer { .. }) {
+            self.fall(world, is_on_ground, frame_time);
+        }
+        if !world.move_h(
+            self.actor,
+            WORLD_SCALE.to_pixels(self.velocity.x * frame_time),
+        ) {
+            self.direction = -self.direction;
+        }
+    }
+
+    fn fall(&mut self, world: &mut World, is_on_ground: bool, frame_time: Duration) {
+        if is_on_ground && self.velocity.y >= Velocity::ZERO {
+            self.velocity.y = Velocity::ZERO;
+        } else {
+            // Enemies fall the way the duck does before any tuning
+            let params = DuckParams::default();
+            let dv = params.gravity_acceleration * frame_time;
+            self.velocity.y = (self.velocity.y + dv).min(params.max_fall_velocity);
+        }
+        if !world.move_v(
+            self.actor,
+            WORLD_SCALE.to_pixels(self.velocity.y * frame_time),
+        ) {
+            self.velocity.y = Velocity::ZERO;
+        }
+    }
+
+    /// Whether there's something to stand on just past the leading bottom corner.
+    fn is_ground_ahead(&self, world: &World) -> bool {
+        let rect = self.rect(world);
+        let front_x = if self.direction > 0.0 {
+            rect.right()
+        } else {
+            rect.left() - 1.0
+        };
+        world.collide_solids(vec2(front_x, rect.bottom()), 1, 2) != Tile::Empty
+    }
+
+    fn draw(&self, world: &World, alpha: f32, viewport: Rect) {
+        let pos = self.previous_pos.lerp(world.actor_pos(self.actor), alpha);
+        let rect = Rect::new(pos.x, pos.y, self.spec.rect.w, self.spec.rect.h);
+        if !self.alive || !rect.overlaps(&viewport) {
+            return;
+        }
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, self.spec.kind.color());
+        let eye_x = if self.direction > 0.0 {
+            rect.right() - 2.0 * EYE_SIZE
+        } else {
+            rect.left() + EYE_SIZE
+        };
+        draw_rectangle(eye_x, rect.y + EYE_SIZE, EYE_SIZE, EYE_SIZE, WHITE);
+    }
+}
+
+/// The level's enemies, each an actor in the platformer world with its own AI.
+#[derive(Default)]
+pub struct Enemies {
+    items: Vec<Enemy>,
+}
+
+impl Enemies {
+    pub fn new(world: &mut World, specs: Vec<EnemySpec>) -> Self {
+        let items = specs
+            .into_iter()
+            .map(|spec| Enemy::new(world, spec))
+            .collect();
+        Self { items }
+    }
+
+    /// Brings every enemy back to life where the map placed it.
+    pub fn reset(&mut self, world: &mut World) {
+        for enemy in &mut self.items {
+            enemy.reset(world);
+        }
+    }
+
+    pub fn step(&mut self, world: &mut World, frame_time: Duration) {
+        for enemy in &mut self.items {
+            enemy.step(world, frame_time);
+        }
+    }
+
+    /// Stomps every enemy the falling duck landed on; touching one any other way hurts the duck.
+    pub fn contact(
+        &mut self,
+        world: &World,
+        duck_rect: Rect,
+        duck_velocity: XY<Velocity>,
+    ) -> Option<Contact> {
+        let mut contact = None;
+        for enemy in self.items.iter_mut().filter(|enemy| enemy.alive) {
+            let rect = enemy.rect(world);
+            if !rect.overlaps(&duck_rect) {
+                continue;
+            }
+            let is_stomp =
+                duck_velocity.y > Velocity::ZERO && duck_rect.bottom() <= rect.top() + STOMP_DEPTH;
+            if is_stomp {
+                enemy.alive = false;
+                contact = Some(Contact::Stomp);
+            } else if contact.is_none() {
+                let center_x = |rect: Rect| rect.x + rect.w / 2.0;
+                let direction = if center_x(duck_rect) < center_x(rect) {
+                    -1.0
+                } else {
+                    1.0
+                };
+                contact = Some(Contact::Hurt { direction });
+            }
+        }
+        contact
+    }
+
+    pub fn draw(&self, world: &World, alpha: f32, viewport: Rect) {
+        for enemy in &self.items {
+            enemy.draw(world, alpha, viewport);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed_timestep::PHYSICS_TIMESTEP;
+    use crate::test_worlds::*;
+
+    /// Floor under columns `floor`, with walls at both ends of the map.
+    fn world(floor: std::ops::Range<usize>) -> World {
+        let mut colliders = vec![Tile::Empty; WIDTH * HEIGHT];
+        colliders[(HEIGHT - 1) * WIDTH..][floor].fill(Tile::Solid);
+        for row in colliders.chunks_mut(WIDTH) {
+            row[0] = Tile::Solid;
+            row[WIDTH - 1] = Tile::Solid;
+        }
+        world_from(colliders)
+    }
+
+    fn enemies(world: &mut World, kind: EnemyKind, pos: Vec2) -> Enemies {
+        let rect = Rect::new(pos.x, pos.y, TILE_SIZE, TILE_SIZE);
+        Enemies::new(world, vec![EnemySpec { kind, rect }])
+    }
+
+    fn run(
+        enemies: &mut Enemies,
+        world: &mut World,
+        duration: Duration,
+        mut check: impl FnMut(Rect),
+    ) {
+        let steps = duration.as_nanos() / PHYSICS_TIMESTEP.as_nanos();
+        for _ in 0..steps {
+            enemies.step(world, PHYSICS_TIMESTEP);
+            check(enemies.items[0].rect(world));
+        }
+    }
+
+    #[test]
+    fn should_turn_walker_at_ledge() {
+        let mut world = world(0..8);
+        let mut enemies = enemies(
+            &mut world,
+            EnemyKind::Walker,
+            vec2(160.0, FLOOR_Y - TILE_SIZE),
+        );
+
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        run(&mut enemies, &mut world, Duration::from_secs(10), |rect| {
+            assert_eq!(rect.bottom(), FLOOR_Y);
+            min_x = min_x.min(rect.x);
+            max_x = max_x.max(rect.right());
+        });
+        assert_eq!(min_x, TILE_SIZE);
+        assert_eq!(max_x, 8.0 * TILE_SIZE);
+    }
+
+    #[test]
+    fn should_hop_frog_back_and_forth() {
+        let mut world = world(0..WIDTH);
+        let start = vec2(320.0, FLOOR_Y - TILE_SIZE);
+        let mut enemies = enemies(&mut world, EnemyKind::Frog, start);
+
+        let mut highest = f32::MAX;
+        run(&mut enemies, &mut world, FROG_HOP_INTERVAL / 2, |rect| {
+            assert_eq!(rect.point(), start);
+        });
+        run(&mut enemies, &mut world, FROG_HOP_INTERVAL, |rect| {
+            highest = highest.min(rect.y);
+        });
+        let first_landing = enemies.items[0].rect(&world);
+        assert!(highest < start.y - TILE_SIZE);
+        assert!(first_landing.x < start.x);
+        assert_eq!(first_landing.bottom(), FLOOR_Y);
+
+        run(&mut enemies, &mut world, FROG_HOP_INTERVAL * 2, |_| {});
+        assert!((enemies.items[0].rect(&world).x - start.x).abs() < 1.0);
+    }
+
+    #[test]
+    fn should_bob_flyer_within_its_patrol() {
+        let mut world = world(0..WIDTH);
+        let start = vec2(320.0, 96.0);
+        let mut enemies = enemies(&mut world, EnemyKind::Flyer, start);
+
+        let mut min = vec2(f32::MAX, f32::MAX);
+        let mut max = vec2(f32::MIN, f32::MIN);
+        run(&mut enemies, &mut world, Duration::from_secs(10), |rect| {
+            min = min.min(rect.point());
+            max = max.max(rect.point());
+        });
+        let range = WORLD_SCALE.to_pixels(FLYER_RANGE);
+        let amplitude = WORLD_SCALE.to_pixels(FLYER_AMPLITUDE);
+        assert!((min.x - (start.x - range)).abs() <= 1.0);
+        assert!((max.x - (start.x + range)).abs() <= 1.0);
+        assert!((min.y - (start.y - amplitude)).abs() <= 1.0);
+        assert!((max.y - (start.y + amplitude)).abs() <= 1.0);
+    }
+
+    #[test]
+    fn should_stomp_from_above_and_hurt_from_the_side() {
+        let mut world = world(0..WIDTH);
+        let mut enemies = enemies(
+            &mut world,
+            EnemyKind::Walker,
+            vec2(320.0, FLOOR_Y - TILE_SIZE),
+        );
+        let falling = XY {
+            x: Velocity::ZERO,
+            y: Velocity::from_meters_on_second(2.0),
+        };
+        let side = Rect::new(300.0, FLOOR_Y - TILE_SIZE, 32.0, 32.0);
+        let above = Rect::new(320.0, FLOOR_Y - 2.0 * TILE_SIZE + 8.0, 32.0, 32.0);
+
+        let from_left = Some(Contact::Hurt { direction: -1.0 });
+        assert_eq!(enemies.contact(&world, side, falling), from_left);
+        assert_eq!(
+            enemies.contact(&world, above, Default::default()),
+            Some(Contact::Hurt { direction: 1.0 })
+        );
+        assert_eq!(
+            enemies.contact(&world, above, falling),
+            Some(Contact::Stomp)
+        );
+        assert_eq!(enemies.contact(&world, side, falling), None);
+
+        enemies.reset(&mut world);
+        assert_eq!(enemies.contact(&world, side, falling), from_left);
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use macroquad::prelude::*;
+
+use crate::assets_server::AssetError;
+use crate::input_service::{key_code_name, Action, InputService};
+use crate::stage::{Stage, Transition};
+use crate::title_screen::draw_centered_text;
+
+const TITLE: &str = "Couldn't load the game";
+const TITLE_FONT_SIZE: f32 = 64.0;
+const MESSAGE_FONT_SIZE: f32 = 28.0;
+const HINT_FONT_SIZE: f32 = 32.0;
+const BACKGROUND: Color = Color::new(0.3, 0.05, 0.05, 1.0);
+
+/// Shown instead of a stage whose assets failed to load; confirming goes back to the stage below.
+pub struct ErrorScreen {
+    error: AssetError,
+    hint: Option<String>,
+}
+
+impl ErrorScreen {
+    pub fn new(error: AssetError) -> Self {
+        Self { error, hint: None }
+    }
+
+    pub fn error(&self) -> &AssetError {
+        &self.error
+    }
+}
+
+#[async_trait(?Send)]
+impl Stage for ErrorScreen {
+    fn tick(&mut self, input: &InputService, _frame_time: Duration) -> Transition {
+        let key_name = |action| input.bindings().keys(action).first().copied();
+        if let (Some(confirm), Some(pause)) = (key_name(Action::Confirm), key_name(Action::Pause)) {
+            self.hint = Some(format!(
+                "{} - back, {} - quit",
+                key_code_name(confirm),
+                key_code_name(pause)
+            ));
+        }
+
+        if input.is_pressed(Action::Confirm) {
+            Transition::Pop
+        } else if input.is_pressed(Action::Pause) {
+            Transition::Quit
+        } else {
+            Transition::None
+        }
+    }
+
+    fn draw(&mut self) {
+        set_default_camera();
+        clear_background(BACKGROUND);
+
+        let center = screen_height() / 2.0;
+        draw_centered_text(TITLE, center - TITLE_FONT_SIZE, TITLE_FONT_SIZE);
+        draw_centered_text(&self.error.to_string(), center, MESSAGE_FONT_SIZE);
+        if let Some(hint) = &self.hint {
+            draw_centered_text(hint, center + 2.0 * HINT_FONT_SIZE, HINT_FONT_SIZE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::KeyCode;
+
+    use super::*;
+
+    fn press(screen: &mut ErrorScreen, key: KeyCode) -> Transition {
+        let mut input = InputService::default();
+        input.update_with(|pressed| pressed == key);
+        screen.tick(&input, Duration::ZERO)
+    }
+
+    fn missing_spawn() -> AssetError {
+        AssetError::MissingObject {
+            path: "map.json".into(),
+            name: "spawn".into(),
+        }
+    }
+
+    #[test]
+    fn should_go_back_on_confirm() {
+        let mut screen = ErrorScreen::new(missing_spawn());
+        assert!(matches!(
+            press(&mut screen, KeyCode::Enter),
+            Transition::Pop
+        ));
+    }
+
+    #[test]
+    fn should_quit_on_pause() {
+        let mut screen = ErrorScreen::new(missing_spawn());
+        assert!(matches!(
+            press(&mut screen, KeyCode::Escape),
+            Transition::Quit
+        ));
+        assert_eq!(
+            screen.error().to_string(),
+            "map.json has no object named \"spawn\""
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+pub const PHYSICS_TIMESTEP: Duration = Duration::from_nanos(1_000_000_000 / 120);
+/// Upper bound on physics steps per rendered frame, so a long stall doesn't snowball.
+pub const MAX_STEPS_PER_FRAME: u32 = 8;
+
+/// Converts variable frame times into a whole number of fixed physics steps.
+pub struct FixedTimestep {
+    timestep: Duration,
+    accumulator: Duration,
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::new(PHYSICS_TIMESTEP)
+    }
+}
+
+impl FixedTimestep {
+    pub fn new(timestep: Duration) -> Self {
+        Self {
+            timestep,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    pub fn timestep(&self) -> Duration {
+        self.timestep
+    }
+
+    /// Accumulates `frame_time` and returns how many physics steps to run now.
+    pub fn advance(&mut self, frame_time: Duration) -> u32 {
+        self.accumulator += frame_time;
+        let steps = (self.accumulator.as_nanos() / self.timestep.as_nanos()) as u32;
+        self.accumulator -= self.timestep * steps;
+        steps.min(MAX_STEPS_PER_FRAME)
+    }
+
+    /// Fraction of the next step already elapsed, for interpolating rendered state.
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.timestep.as_secs_f32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_carry_remainder_between_frames() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+        assert_eq!(timestep.advance(Duration::from_millis(15)), 1);
+        assert!((timestep.alpha() - 0.5).abs() < 1e-6);
+        assert_eq!(timestep.advance(Duration::from_millis(5)), 1);
+        assert_eq!(timestep.alpha(), 0.0);
+    }
+
+    #[test]
+    fn should_run_no_steps_for_short_frames() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+        assert_eq!(timestep.advance(Duration::from_millis(4)), 0);
+        assert_eq!(timestep.advance(Duration::from_millis(4)), 0);
+        assert_eq!(timestep.advance(Duration::from_millis(4)), 1);
+    }
+
+    #[test]
+    fn should_drop_steps_beyond_limit() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+        assert_eq!(
+            timestep.advance(Duration::from_secs(1)),
+            MAX_STEPS_PER_FRAME
+        );
+        assert_eq!(timestep.advance(Duration::ZERO), 0);
+    }
+}
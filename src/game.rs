@@ -0,0 +1,652 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use macroquad::prelude::*;
+use macroquad_platformer::*;
+
+use crate::animation::SpriteSheet;
+use crate::assets_server::{AssetError, AssetWatcher, AssetsServer};
+use crate::camera::Camera;
+use crate::campaign::SharedCampaign;
+use crate::checkpoints::{CheckpointSnapshot, Checkpoints};
+use crate::collectibles::{Collectibles, Tally};
+use crate::duck::{Duck, DuckAbilities, DuckInput, MAX_HEALTH};
+use crate::duck_animation::DUCK_CLIPS;
+use crate::duck_params::DuckParams;
+use crate::duck_world::DuckWorld;
+use crate::enemies::{Contact, Enemies, EnemySpec, STOMP_BOUNCE_VELOCITY};
+use crate::error_screen::ErrorScreen;
+use crate::fixed_timestep::FixedTimestep;
+use crate::game_over::GameOver;
+use crate::hazards::Hazards;
+use crate::input_service::{Action, InputService};
+use crate::level_complete::{LevelComplete, LevelSummary};
+use crate::moving_platforms::{MovingPlatforms, PlatformSpec};
+use crate::pause_menu::{PauseMenu, PauseMenuItem};
+use crate::slopes::Slopes;
+use crate::stage::{Stage, Transition};
+use crate::surfaces::Surfaces;
+use crate::tiled_map::TiledMap;
+use crate::water::{Splashes, Water};
+
+const VIEWPORT_HEIGHT: f32 = 720.0;
+const HUD_FONT_SIZE: f32 = 32.0;
+const HUD_MARGIN: f32 = 16.0;
+/// How long the duck's death plays before it respawns.
+const DEATH_DURATION: Duration = Duration::from_millis(600);
+const DUCK_PARAMS_PATH: &str = "duck.ron";
+const DUCK_TEXTURE_PATH: &str = "duck.png";
+/// Aseprite export of the duck's animations over `DUCK_TEXTURE_PATH`.
+const DUCK_SHEET_PATH: &str = "duck.json";
+const TILESET_PATH: &str = "tmw_desert_spacing.json";
+const TILESET_TEXTURE_PATH: &str = "tmw_desert_spacing.png";
+
+/// Names of the objects and properties level designers set in Tiled.
+mod level_metadata {
+    /// Point where the duck's feet start.
+    pub const SPAWN: &str = "spawn";
+    /// Rectangle that finishes the level when the duck touches it.
+    pub const EXIT: &str = "exit";
+    /// Tile layer property, `solid` or `jumpthrough`.
+    pub const COLLISION: &str = "collision";
+    /// Map properties; `true` lets the duck slide down or jump off walls in that level.
+    pub const WALL_SLIDE: &str = "wallslide";
+    pub const WALL_JUMP: &str = "walljump";
+}
+
+async fn load_map(assets_server: &AssetsServer, map_path: &str) -> Result<TiledMap, AssetError> {
+    let tiled_map_json = assets_server.load_string(map_path).await?;
+    let tileset_json = assets_server.load_string(TILESET_PATH).await?;
+    let tileset_png = assets_server.load_texture(TILESET_TEXTURE_PATH).await?;
+    TiledMap::parse(
+        map_path,
+        &tiled_map_json,
+        &[(TILESET_PATH, &tileset_json)],
+        &[(TILESET_TEXTURE_PATH, tileset_png)],
+    )
+}
+
+/// Tile layers marked with a `collision` property, with the collider each one adds.
+fn collider_layers(
+    tiled_map: &TiledMap,
+    map_path: &str,
+) -> Result<Vec<(String, Tile)>, AssetError> {
+    let mut colliders = vec![];
+    for layer in tiled_map.tile_layers() {
+        let Some(role) = tiled_map.layer_property(layer, level_metadata::COLLISION) else {
+            continue;
+        };
+        let tile = match role.as_str() {
+            Some("solid") => Tile::Solid,
+            Some("jumpthrough") => Tile::JumpThrough,
+            _ => {
+                return Err(AssetError::InvalidProperty {
+                    path: map_path.into(),
+                    cause: format!(
+                        "layer \"{}\" has {}={}, expected solid or jumpthrough",
+                        layer,
+                        level_metadata::COLLISION,
+                        role
+                    ),
+                })
+            }
+        };
+        colliders.push((layer.to_string(), tile));
+    }
+    // Without a collision layer the duck would fall straight out of the level
+    if colliders.is_empty() {
+        return Err(AssetError::MissingLayer {
+            path: map_path.into(),
+            role: format!(
+                "{}=solid or {}=jumpthrough",
+                level_metadata::COLLISION,
+                level_metadata::COLLISION
+            ),
+            layers: tiled_map.tile_layers().map(String::from).collect(),
+        });
+    }
+    Ok(colliders)
+}
+
+/// Abilities the map switches on; a missing property leaves the ability off.
+fn duck_abilities(tiled_map: &TiledMap, map_path: &str) -> Result<DuckAbilities, AssetError> {
+    let flag = |name: &str| match tiled_map.properties().get(name) {
+        Some(value) => value.as_bool().ok_or_else(|| AssetError::InvalidProperty {
+            path: map_path.into(),
+            cause: format!("map has {}={}, expected true or false", name, value),
+        }),
+        None => Ok(false),
+    };
+    Ok(DuckAbilities {
+        wall_slide: flag(level_metadata::WALL_SLIDE)?,
+        wall_jump: flag(level_metadata::WALL_JUMP)?,
+    })
+}
+
+fn spawn_point(tiled_map: &TiledMap, map_path: &str) -> Result<Vec2, AssetError> {
+    tiled_map
+        .object(level_metadata::SPAWN)
+        .map(|spawn| spawn.rect.point())
+        .ok_or_else(|| AssetError::MissingObject {
+            path: map_path.into(),
+            name: level_metadata::SPAWN.into(),
+        })
+}
+
+async fn load_duck_sheet(assets_server: &AssetsServer) -> Result<SpriteSheet, AssetError> {
+    let texture = assets_server.load_texture(DUCK_TEXTURE_PATH).await?;
+    let json = assets_server.load_string(DUCK_SHEET_PATH).await?;
+    let sheet = SpriteSheet::from_json(&json, texture)
+        .map_err(|err| AssetError::parse(DUCK_SHEET_PATH, err))?;
+    sheet
+        .require_clips(&DUCK_CLIPS)
+        .map_err(|err| AssetError::parse(DUCK_SHEET_PATH, err))?;
+    Ok(sheet)
+}
+
+pub async fn load_duck_params(assets_server: &AssetsServer) -> Result<DuckParams, AssetError> {
+    let duck_params_ron = assets_server.load_string(DUCK_PARAMS_PATH).await?;
+    DuckParams::from_ron(&duck_params_ron).map_err(|err| AssetError::parse(DUCK_PARAMS_PATH, err))
+}
+
+/// One campaign level; reaching its exit moves on to the next one.
+pub struct Game {
+    campaign: SharedCampaign,
+    level_index: usize,
+    state: GameState,
+}
+
+#[derive(Default)]
+enum GameState {
+    #[default]
+    Unloaded,
+    Loaded(Box<Level>),
+    Failed(AssetError),
+}
+
+impl Game {
+    pub fn new(campaign: SharedCampaign, level_index: usize) -> Self {
+        Self {
+            campaign,
+            level_index,
+            state: GameState::Unloaded,
+        }
+    }
+
+    /// Marks the level done and shows how it went.
+    fn complete_level(&mut self, summary: LevelSummary) -> Transition {
+        let mut campaign = self.campaign.borrow_mut();
+        campaign.complete(self.level_index);
+        campaign.record_time(self.level_index, summary.time);
+        let level_complete =
+            LevelComplete::new(Rc::clone(&self.campaign), self.level_index, summary);
+        Transition::Replace(Box::new(level_complete))
+    }
+}
+
+pub struct Level {
+    world: DuckWorld,
+    duck: Duck,
+    camera: Camera,
+    timestep: FixedTimestep,
+    /// Simulated time since the start or last restart, counted in physics steps the duck was
+    /// alive for.
+    elapsed: Duration,
+    pending_jump_press: bool,
+    spawn: Vec2,
+    /// What the duck comes back to after dying.
+    checkpoint: CheckpointSnapshot,
+    /// Time left of the death animation while the duck is dead; it stays at zero once the duck
+    /// is out of health.
+    death_left: Option<Duration>,
+    exit: Option<Rect>,
+    collectibles: Collectibles,
+    checkpoints: Checkpoints,
+    hazards: Hazards,
+    platforms: MovingPlatforms,
+    enemies: Enemies,
+    water: Water,
+    splashes: Splashes,
+    pause_choice: Rc<Cell<Option<PauseMenuItem>>>,
+    /// Missing while the campaign pins the duck params.
+    duck_params_watcher: Option<AssetWatcher>,
+}
+
+impl Level {
+    async fn load(
+        assets_server: &AssetsServer,
+        map_path: &str,
+        pinned_duck_params: Option<DuckParams>,
+    ) -> Result<Self, AssetError> {
+        let tiled_map = load_map(assets_server, map_path).await?;
+        let map_size = tiled_map.size();
+        let colliders = collider_layers(&tiled_map, map_path)?;
+        let spawn_feet = spawn_point(&tiled_map, map_path)?;
+        let abilities = duck_abilities(&tiled_map, map_path)?;
+        let exit = tiled_map.object(level_metadata::EXIT).map(|exit| exit.rect);
+        let collectibles = Collectibles::from_map(&tiled_map, map_path)?;
+        let checkpoints = Checkpoints::from_map(&tiled_map);
+        let hazards = Hazards::from_map(&tiled_map, map_path)?;
+        let platform_specs = PlatformSpec::from_map(&tiled_map, map_path)?;
+        let enemy_specs = EnemySpec::from_map(&tiled_map, map_path)?;
+        let water = Water::from_map(&tiled_map);
+        let surfaces = Surfaces::from_map(&tiled_map, map_path)?;
+        let slopes = Slopes::from_map(&tiled_map, map_path)?;
+        let aspect_ratio = screen_width() / screen_height();
+        let viewport_size = vec2(aspect_ratio * VIEWPORT_HEIGHT, VIEWPORT_HEIGHT);
+
+        let mut world = DuckWorld::new(tiled_map);
+        let duck_sheet = load_duck_sheet(assets_server).await?;
+
+        for (layer, tile) in colliders {
+            world.add_static_colliders(&layer, tile);
+        }
+        let platforms = MovingPlatforms::new(&mut world, platform_specs);
+        let enemies = Enemies::new(&mut world, enemy_specs);
+
+        let duck_size = duck_sheet.frame_size();
+        let spawn = spawn_feet - vec2(duck_size.x / 2.0, duck_size.y);
+        let mut duck = Duck::create(duck_sheet, &mut world, spawn);
+        let duck_params = match pinned_duck_params {
+            Some(duck_params) => duck_params,
+            None => load_duck_params(assets_server).await?,
+        };
+        duck.set_params(duck_params);
+        duck.set_abilities(abilities);
+        duck.set_water(water.clone());
+        duck.set_surfaces(surfaces);
+        duck.set_slopes(slopes);
+        let camera = Camera::new(map_size, viewport_size);
+        let checkpoint = CheckpointSnapshot {
+            duck_pos: spawn,
+            collectibles: collectibles.clone(),
+            camera: camera.state(),
+        };
+
+        Ok(Self {
+            world,
+            duck,
+            camera,
+            timestep: FixedTimestep::default(),
+            elapsed: Duration::ZERO,
+            pending_jump_press: false,
+            spawn,
+            checkpoint,
+            death_left: None,
+            exit,
+            collectibles,
+            checkpoints,
+            hazards,
+            platforms,
+            enemies,
+            water,
+            splashes: Splashes::default(),
+            pause_choice: Default::default(),
+            duck_params_watcher: pinned_duck_params
+                .is_none()
+                .then(|| assets_server.watch(DUCK_PARAMS_PATH)),
+        })
+    }
+
+    /// Puts the level back to its loaded state without reloading assets.
+    fn restart(&mut self) {
+        self.collectibles.reset();
+        self.checkpoints.reset();
+        self.camera.reset();
+        self.platforms.reset(&mut self.world);
+        self.splashes.clear();
+        self.duck.restore_health();
+        self.checkpoint = CheckpointSnapshot {
+            duck_pos: self.spawn,
+            collectibles: self.collectibles.clone(),
+            camera: self.camera.state(),
+        };
+        self.respawn();
+        self.timestep = FixedTimestep::default();
+        self.elapsed = Duration::ZERO;
+        self.pending_jump_press = false;
+    }
+
+    /// Puts the duck at rest, the pickups and the camera back as they were at the last checkpoint,
+    /// and every enemy back where the map placed it.
+    fn respawn(&mut self) {
+        self.duck.respawn(&mut self.world, self.checkpoint.duck_pos);
+        self.enemies.reset(&mut self.world);
+        self.collectibles = self.checkpoint.collectibles.clone();
+        self.camera.restore(&self.checkpoint.camera);
+        self.death_left = None;
+    }
+
+    /// Saves a snapshot with the duck standing on the middle of the checkpoint's floor.
+    fn save_checkpoint(&mut self, checkpoint: Rect) {
+        let size = self.duck.size();
+        self.checkpoint = CheckpointSnapshot {
+            duck_pos: vec2(
+                checkpoint.x + (checkpoint.w - size.x) / 2.0,
+                checkpoint.bottom() - size.y,
+            ),
+            collectibles: self.collectibles.clone(),
+            camera: self.camera.state(),
+        };
+    }
+
+    /// Swaps in edited duck params; a broken file is reported and the old params kept.
+    fn reload_duck_params(&mut self) {
+        let Some(watcher) = &self.duck_params_watcher else {
+            return;
+        };
+        let params = watcher
+            .read_string()
+            .map_err(|err| err.to_string())
+            .and_then(|ron| DuckParams::from_ron(&ron).map_err(|err| err.to_string()));
+        match params {
+            Ok(params) => {
+                self.duck.set_params(params);
+                info!("Reloaded {}", DUCK_PARAMS_PATH);
+            }
+            Err(err) => error!(
+                "Keeping previous duck params, {}: {}",
+                DUCK_PARAMS_PATH, err
+            ),
+        }
+    }
+
+    fn tick(&mut self, input: &InputService, frame_time: Duration) -> Transition {
+        let duck_params_changed = self
+            .duck_params_watcher
+            .as_mut()
+            .is_some_and(|watcher| watcher.poll(frame_time));
+        if duck_params_changed {
+            self.reload_duck_params();
+        }
+
+        if self.pause_choice.take() == Some(PauseMenuItem::Restart) {
+            self.restart();
+        }
+
+        if input.is_pressed(Action::Pause) {
+            let pause_menu = PauseMenu::new(Rc::clone(&self.pause_choice));
+            return Transition::Push(Box::new(pause_menu));
+        }
+
+        let mut duck_input = DuckInput::from(input);
+        // A press during a frame too short for a physics step waits for the next step
+        duck_input.jump_pressed |= self.pending_jump_press;
+        let steps = self.timestep.advance(frame_time);
+        self.pending_jump_press = duck_input.jump_pressed && steps == 0;
+
+        for _ in 0..steps {
+            let timestep = self.timestep.timestep();
+            if let Some(death_left) = &mut self.death_left {
+                *death_left = death_left.saturating_sub(timestep);
+                if death_left.is_zero() && self.duck.is_alive() {
+                    self.respawn();
+                }
+                self.camera.follow(self.duck.center(&self.world, 1.0));
+                continue;
+            }
+
+            // Only live steps count, so dying doesn't cost the player best-time seconds
+            self.elapsed += timestep;
+            let previous_duck_rect = self.duck.rect(&self.world);
+            self.duck.step(&mut self.world, duck_input, timestep);
+            self.camera.follow(self.duck.center(&self.world, 1.0));
+            self.platforms.step(&mut self.world, &self.duck, timestep);
+            self.enemies.step(&mut self.world, timestep);
+            self.splashes.step(timestep);
+            duck_input.jump_pressed = false;
+            let duck_rect = self.duck.rect(&self.world);
+            self.splash_on_crossing(previous_duck_rect, duck_rect);
+            self.collectibles.collect_overlapping(duck_rect);
+            if let Some(checkpoint) = self.checkpoints.reach_overlapping(duck_rect) {
+                self.save_checkpoint(checkpoint);
+            }
+            let contact = self
+                .enemies
+                .contact(&self.world, duck_rect, self.duck.velocity());
+            match contact {
+                Some(Contact::Stomp) => self.duck.bounce(STOMP_BOUNCE_VELOCITY),
+                Some(Contact::Hurt { direction }) => {
+                    self.duck.hurt(direction);
+                }
+                None => {}
+            }
+            // Hazards ignore invulnerability and always send the duck back to the checkpoint
+            let is_on_hazard = self.hazards.is_deadly(duck_rect);
+            if is_on_hazard {
+                self.duck.lose_health();
+            }
+            if is_on_hazard || !self.duck.is_alive() {
+                self.death_left = Some(DEATH_DURATION);
+            }
+        }
+        Transition::None
+    }
+
+    /// Splashes where the duck went into or came out of a water volume.
+    fn splash_on_crossing(&mut self, previous_duck_rect: Rect, duck_rect: Rect) {
+        let before = self.water.volume_overlapping(previous_duck_rect);
+        let after = self.water.volume_overlapping(duck_rect);
+        if let Some(volume) = before.xor(after) {
+            self.splashes
+                .spawn(vec2(duck_rect.x + duck_rect.w / 2.0, volume.top()));
+        }
+    }
+
+    fn is_duck_at_exit(&self) -> bool {
+        if self.death_left.is_some() {
+            return false;
+        }
+        let duck_rect = self.duck.rect(&self.world);
+        self.exit.is_some_and(|exit| exit.overlaps(&duck_rect))
+    }
+
+    /// Whether the duck ran out of health and its death has played out.
+    fn is_game_over(&self) -> bool {
+        self.death_left.is_some_and(|left| left.is_zero())
+    }
+
+    fn summary(&self) -> LevelSummary {
+        LevelSummary {
+            time: self.elapsed,
+            tallies: self.collectibles.tallies(),
+        }
+    }
+
+    fn draw(&mut self) {
+        let alpha = self.timestep.alpha();
+        self.camera
+            .update_viewport(self.duck.center(&self.world, alpha));
+        self.camera.focus();
+
+        self.world.draw(self.camera.viewport());
+        self.platforms.draw(alpha, self.camera.viewport());
+        self.checkpoints.draw(self.camera.viewport());
+        self.collectibles.draw(self.camera.viewport());
+        self.enemies
+            .draw(&self.world, alpha, self.camera.viewport());
+        match self.death_left {
+            Some(death_left) => {
+                let progress = 1.0 - death_left.as_secs_f32() / DEATH_DURATION.as_secs_f32();
+                self.duck.draw_dying(&self.world, progress);
+            }
+            None => self.duck.draw(&self.world, alpha),
+        }
+        self.water.draw(self.camera.viewport());
+        self.splashes.draw();
+
+        self.draw_hud();
+    }
+
+    fn draw_hud(&self) {
+        set_default_camera();
+        let health = format!("health {}/{}", self.duck.health(), MAX_HEALTH);
+        let hud = std::iter::once(health)
+            .chain(self.collectibles.tallies().iter().map(Tally::to_string))
+            .collect::<Vec<_>>()
+            .join("   ");
+        draw_text(
+            &hud,
+            HUD_MARGIN,
+            HUD_MARGIN + HUD_FONT_SIZE / 2.0,
+            HUD_FONT_SIZE,
+            WHITE,
+        );
+    }
+}
+
+#[async_trait(?Send)]
+impl Stage for Game {
+    async fn load(&mut self) {
+        let assets_server = AssetsServer::new("assets");
+        let (map_path, duck_params) = {
+            let campaign = self.campaign.borrow();
+            let map_path = campaign.levels()[self.level_index].map.clone();
+            (map_path, campaign.duck_params())
+        };
+        self.state = match Level::load(&assets_server, &map_path, duck_params).await {
+            Ok(level) => GameState::Loaded(Box::new(level)),
+            Err(err) => GameState::Failed(err),
+        };
+    }
+
+    fn tick(&mut self, input: &InputService, frame_time: Duration) -> Transition {
+        match &mut self.state {
+            GameState::Loaded(level) => {
+                let transition = level.tick(input, frame_time);
+                if level.is_game_over() {
+                    let game_over = GameOver::new(Rc::clone(&self.campaign), self.level_index);
+                    return Transition::Replace(Box::new(game_over));
+                }
+                if level.is_duck_at_exit() {
+                    let summary = level.summary();
+                    return self.complete_level(summary);
+                }
+                transition
+            }
+            GameState::Failed(_) => {
+                let GameState::Failed(err) = std::mem::take(&mut self.state) else {
+                    unreachable!()
+                };
+                Transition::Replace(Box::new(ErrorScreen::new(err)))
+            }
+            GameState::Unloaded => panic!("Can't call tick before loading"),
+        }
+    }
+
+    fn draw(&mut self) {
+        match &mut self.state {
+            GameState::Loaded(level) => level.draw(),
+            // Replaced by the error screen before anything is drawn
+            GameState::Failed(_) => {}
+            GameState::Unloaded => panic!("Can't call draw before loading"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaign::{Campaign, CAMPAIGN_PATH};
+
+    const MAP_PATH: &str = "map.json";
+    const MAP_JSON: &str = include_str!("../assets/map.json");
+    const TILESET_JSON: &str = include_str!("../assets/tmw_desert_spacing.json");
+
+    fn parse(tiled_map_json: &str) -> TiledMap {
+        TiledMap::parse(
+            MAP_PATH,
+            tiled_map_json,
+            &[(TILESET_PATH, TILESET_JSON)],
+            &[(TILESET_TEXTURE_PATH, Texture2D::empty())],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_read_level_metadata_from_every_campaign_map() {
+        let campaign_ron = std::fs::read_to_string(format!("assets/{}", CAMPAIGN_PATH)).unwrap();
+        for level in Campaign::from_ron(&campaign_ron).unwrap().levels() {
+            let map_json = std::fs::read_to_string(format!("assets/{}", level.map)).unwrap();
+            let tiled_map = parse(&map_json);
+            let colliders = collider_layers(&tiled_map, &level.map).unwrap();
+            assert!(colliders.iter().any(|(_, tile)| *tile == Tile::Solid));
+            assert!(tiled_map.object(level_metadata::EXIT).is_some());
+            let collectibles = Collectibles::from_map(&tiled_map, &level.map).unwrap();
+            assert!(!collectibles.tallies().is_empty());
+            PlatformSpec::from_map(&tiled_map, &level.map).unwrap();
+            EnemySpec::from_map(&tiled_map, &level.map).unwrap();
+            Surfaces::from_map(&tiled_map, &level.map).unwrap();
+            Slopes::from_map(&tiled_map, &level.map).unwrap();
+            duck_abilities(&tiled_map, &level.map).unwrap();
+
+            let spawn = spawn_point(&tiled_map, &level.map).unwrap();
+            let bounds = Rect::new(0.0, 0.0, tiled_map.size().x, tiled_map.size().y);
+            assert!(
+                bounds.contains(spawn),
+                "{} spawns outside the map",
+                level.map
+            );
+            let hazards = Hazards::from_map(&tiled_map, &level.map).unwrap();
+            let duck_at_spawn = Rect::new(spawn.x - 16.0, spawn.y - 32.0, 32.0, 32.0);
+            assert!(
+                !hazards.is_deadly(duck_at_spawn),
+                "{} spawns on a hazard",
+                level.map
+            );
+        }
+    }
+
+    #[test]
+    fn should_report_missing_spawn() {
+        let tiled_map = parse(&MAP_JSON.replace("\"spawn\"", "\"start\""));
+        assert!(matches!(
+            spawn_point(&tiled_map, MAP_PATH),
+            Err(AssetError::MissingObject { name, .. }) if name == level_metadata::SPAWN
+        ));
+    }
+
+    #[test]
+    fn should_read_duck_abilities_from_map_properties() {
+        let tiled_map = parse(MAP_JSON);
+        assert_eq!(
+            duck_abilities(&tiled_map, MAP_PATH).unwrap(),
+            DuckAbilities::default()
+        );
+
+        let oasis_json = std::fs::read_to_string("assets/oasis.json").unwrap();
+        let abilities = duck_abilities(&parse(&oasis_json), "oasis.json").unwrap();
+        assert!(abilities.wall_slide && abilities.wall_jump);
+
+        let broken = parse(&oasis_json.replace(
+            r#""name":"walljump",
+         "type":"bool",
+         "value":true"#,
+            r#""name":"walljump",
+         "type":"string",
+         "value":"yes""#,
+        ));
+        let err = duck_abilities(&broken, "oasis.json").err().unwrap();
+        assert!(err.to_string().contains("walljump=yes"));
+    }
+
+    #[test]
+    fn should_reject_unknown_collision_role() {
+        let tiled_map = parse(&MAP_JSON.replace("\"jumpthrough\"", "\"bouncy\""));
+        let err = collider_layers(&tiled_map, MAP_PATH).err().unwrap();
+        assert!(matches!(err, AssetError::InvalidProperty { .. }));
+        assert!(err.to_string().contains("collision=bouncy"));
+    }
+
+    #[test]
+    fn should_reject_map_without_collision_layer() {
+        let tiled_map = parse(&MAP_JSON.replace("\"collision\"", "\"decoration\""));
+        let err = collider_layers(&tiled_map, MAP_PATH).err().unwrap();
+        assert!(matches!(err, AssetError::MissingLayer { .. }));
+        assert!(err.to_string().starts_with(
+            "map.json has no layer with collision=solid or collision=jumpthrough, only "
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use macroquad::prelude::*;
+
+use crate::campaign::SharedCampaign;
+use crate::game::Game;
+use crate::input_service::{key_code_name, Action, InputService};
+use crate::stage::{Stage, Transition};
+use crate::title_screen::draw_centered_text;
+
+const TITLE: &str = "Game over";
+const TITLE_FONT_SIZE: f32 = 64.0;
+const HINT_FONT_SIZE: f32 = 32.0;
+const BACKGROUND: Color = Color::new(0.1, 0.1, 0.15, 1.0);
+
+/// Shown once the duck runs out of health; confirming retries the level from its start.
+pub struct GameOver {
+    campaign: SharedCampaign,
+    level_index: usize,
+    hint: Option<String>,
+}
+
+impl GameOver {
+    pub fn new(campaign: SharedCampaign, level_index: usize) -> Self {
+        Self {
+            campaign,
+            level_index,
+            hint: None,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Stage for GameOver {
+    fn tick(&mut self, input: &InputService, _frame_time: Duration) -> Transition {
+        let key_name = |action| input.bindings().keys(action).first().copied();
+        if let (Some(confirm), Some(pause)) = (key_name(Action::Confirm), key_name(Action::Pause)) {
+            self.hint = Some(format!(
+                "{} - retry, {} - back",
+                key_code_name(confirm),
+                key_code_name(pause)
+            ));
+        }
+
+        if input.is_pressed(Action::Confirm) {
+            let game = Game::new(Rc::clone(&self.campaign), self.level_index);
+            Transition::Replace(Box::new(game))
+        } else if input.is_pressed(Action::Pause) {
+            Transition::Pop
+        } else {
+            Transition::None
+        }
+    }
+
+    fn draw(&mut self) {
+        set_default_camera();
+        clear_background(BACKGROUND);
+
+        let center = screen_height() / 2.0;
+        draw_centered_text(TITLE, center - TITLE_FONT_SIZE / 2.0, TITLE_FONT_SIZE);
+        if let Some(hint) = &self.hint {
+            draw_centered_text(hint, center + TITLE_FONT_SIZE, HINT_FONT_SIZE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use macroquad::prelude::KeyCode;
+
+    use super::*;
+    use crate::campaign::{Campaign, LevelInfo};
+
+    fn press(screen: &mut GameOver, key: KeyCode) -> Transition {
+        let mut input = InputService::default();
+        input.update_with(|pressed| pressed == key);
+        screen.tick(&input, Duration::ZERO)
+    }
+
+    fn screen() -> GameOver {
+        let campaign = Campaign::new(vec![LevelInfo {
+            name: "a".into(),
+            map: "a.json".into(),
+        }]);
+        GameOver::new(Rc::new(RefCell::new(campaign)), 0)
+    }
+
+    #[test]
+    fn should_retry_or_go_back() {
+        assert!(matches!(
+            press(&mut screen(), KeyCode::Enter),
+            Transition::Replace(_)
+        ));
+        assert!(matches!(
+            press(&mut screen(), KeyCode::Escape),
+            Transition::Pop
+        ));
+    }
+}
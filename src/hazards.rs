@@ -0,0 +1,80 @@
+use macroquad::prelude::Rect;
+
+use crate::assets_server::AssetError;
+use crate::tiled_map::{TileGrid, TiledMap};
+
+/// Tileset tile property; `true` makes every placement of the tile deadly.
+pub const HAZARD_PROPERTY: &str = "hazard";
+
+/// Grid of deadly tiles across all layers; falling below the map is deadly too.
+pub struct Hazards {
+    cells: Vec<bool>,
+    grid: TileGrid,
+}
+
+impl Hazards {
+    pub fn from_map(tiled_map: &TiledMap, map_path: &str) -> Result<Self, AssetError> {
+        let grid = tiled_map.grid();
+        let mut cells = vec![false; grid.len()];
+        for (x, y, hazard) in tiled_map.tiles_with_property(HAZARD_PROPERTY) {
+            let is_hazard = hazard
+                .as_bool()
+                .ok_or_else(|| AssetError::InvalidProperty {
+                    path: map_path.into(),
+                    cause: format!(
+                        "tile at ({}, {}) has {}={}, expected true or false",
+                        x, y, HAZARD_PROPERTY, hazard
+                    ),
+                })?;
+            cells[grid.index(x, y)] |= is_hazard;
+        }
+        Ok(Self { cells, grid })
+    }
+
+    /// Whether `rect` overlaps a hazard tile or has fallen entirely below the map.
+    pub fn is_deadly(&self, rect: Rect) -> bool {
+        rect.top() >= self.grid.size().y
+            || self
+                .grid
+                .cells_overlapping(rect)
+                .any(|(x, y)| self.cells[self.grid.index(x, y)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tiled_map::test_maps::*;
+
+    /// The desert tileset's cactus, marked as a hazard.
+    const CACTUS: u32 = 31;
+
+    fn hazards(data: [u32; 3]) -> Result<Hazards, AssetError> {
+        Hazards::from_map(&ground_map(3, &data, DESERT_TILESET_JSON), "map.json")
+    }
+
+    #[test]
+    fn should_kill_on_hazard_tile_only() {
+        let hazards = hazards([1, CACTUS, 1]).unwrap();
+        assert!(!hazards.is_deadly(Rect::new(0.0, 0.0, 32.0, 32.0)));
+        assert!(hazards.is_deadly(Rect::new(16.0, 0.0, 32.0, 32.0)));
+        assert!(!hazards.is_deadly(Rect::new(64.0, 0.0, 32.0, 32.0)));
+    }
+
+    #[test]
+    fn should_kill_below_map() {
+        let hazards = hazards([1, 1, 1]).unwrap();
+        assert!(!hazards.is_deadly(Rect::new(0.0, 16.0, 32.0, 32.0)));
+        assert!(hazards.is_deadly(Rect::new(0.0, 32.0, 32.0, 32.0)));
+    }
+
+    #[test]
+    fn should_reject_non_bool_hazard() {
+        let tileset_json = DESERT_TILESET_JSON
+            .replace(r#""type":"bool""#, r#""type":"string""#)
+            .replace(r#""value":true"#, r#""value":"spikes""#);
+        let tiled_map = ground_map(1, &[CACTUS], &tileset_json);
+        let err = Hazards::from_map(&tiled_map, "map.json").err().unwrap();
+        assert!(err.to_string().contains("hazard=spikes"));
+    }
+}
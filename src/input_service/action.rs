@@ -0,0 +1,73 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Descend,
+    Pause,
+    MenuUp,
+    MenuDown,
+    Confirm,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Descend,
+        Action::Pause,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::Confirm,
+    ];
+
+    /// Position in `Action::ALL`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveLeft => "MoveLeft",
+            Action::MoveRight => "MoveRight",
+            Action::Jump => "Jump",
+            Action::Descend => "Descend",
+            Action::Pause => "Pause",
+            Action::MenuUp => "MenuUp",
+            Action::MenuDown => "MenuDown",
+            Action::Confirm => "Confirm",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionState {
+    held: bool,
+    pressed: bool,
+    released: bool,
+}
+
+impl ActionState {
+    pub fn update(&mut self, held: bool) {
+        self.pressed = held && !self.held;
+        self.released = !held && self.held;
+        self.held = held;
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    pub fn is_released(&self) -> bool {
+        self.released
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use macroquad::prelude::KeyCode;
+use nanoserde::{DeJson, DeJsonErr};
+
+use super::action::Action;
+use super::gamepad::GamepadButton;
+
+/// Keys and gamepad buttons bound to every action; an action may have several of each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bindings {
+    keys: HashMap<Action, Vec<KeyCode>>,
+    buttons: HashMap<Action, Vec<GamepadButton>>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self {
+            keys: HashMap::from([
+                (Action::MoveLeft, vec![KeyCode::Left]),
+                (Action::MoveRight, vec![KeyCode::Right]),
+                (Action::Jump, vec![KeyCode::Space]),
+                (Action::Descend, vec![KeyCode::Down]),
+                (Action::Pause, vec![KeyCode::Escape]),
+                (Action::MenuUp, vec![KeyCode::Up]),
+                (Action::MenuDown, vec![KeyCode::Down]),
+                (Action::Confirm, vec![KeyCode::Enter, KeyCode::Space]),
+            ]),
+            buttons: HashMap::from([
+                (Action::MoveLeft, vec![GamepadButton::DPadLeft]),
+                (Action::MoveRight, vec![GamepadButton::DPadRight]),
+                (Action::Jump, vec![GamepadButton::South]),
+                (Action::Descend, vec![GamepadButton::DPadDown]),
+                (Action::Pause, vec![GamepadButton::Start]),
+                (Action::MenuUp, vec![GamepadButton::DPadUp]),
+                (Action::MenuDown, vec![GamepadButton::DPadDown]),
+                (Action::Confirm, vec![GamepadButton::South]),
+            ]),
+        }
+    }
+}
+
+impl Bindings {
+    /// Parses `{ "Action": ["Key or Button", ...] }`; actions missing from the file keep defaults.
+    pub fn from_json(json: &str) -> Result<Self, BindingsError> {
+        let config: HashMap<String, Vec<String>> =
+            DeJson::deserialize_json(json).map_err(BindingsError::Parse)?;
+
+        let mut bindings = Self::default();
+        bindings.override_with(&config)?;
+        Ok(bindings)
+    }
+
+    /// Replaces the keys and buttons of every action named in `config`, leaving the rest. Nothing
+    /// changes unless every entry is valid.
+    pub fn override_with(
+        &mut self,
+        config: &HashMap<String, Vec<String>>,
+    ) -> Result<(), BindingsError> {
+        let mut overridden = self.clone();
+        for (action_name, key_names) in config {
+            let action = Action::from_name(action_name)
+                .ok_or_else(|| BindingsError::UnknownAction(action_name.clone()))?;
+            let mut keys = vec![];
+            let mut buttons = vec![];
+            for name in key_names {
+                if let Some(key) = key_code_from_name(name) {
+                    keys.push(key);
+                } else if let Some(button) = GamepadButton::from_name(name) {
+                    buttons.push(button);
+                } else {
+                    return Err(BindingsError::UnknownKey(name.clone()));
+                }
+            }
+            overridden.keys.insert(action, keys);
+            overridden.buttons.insert(action, buttons);
+        }
+        *self = overridden;
+        Ok(())
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.keys.get(&action).map_or(&[], |keys| keys.as_slice())
+    }
+
+    pub fn buttons(&self, action: Action) -> &[GamepadButton] {
+        self.buttons
+            .get(&action)
+            .map_or(&[], |buttons| buttons.as_slice())
+    }
+
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        let keys = self.keys.entry(action).or_default();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    pub fn unbind(&mut self, action: Action, key: KeyCode) {
+        if let Some(keys) = self.keys.get_mut(&action) {
+            keys.retain(|bound| *bound != key);
+        }
+    }
+
+    pub fn bind_button(&mut self, action: Action, button: GamepadButton) {
+        let buttons = self.buttons.entry(action).or_default();
+        if !buttons.contains(&button) {
+            buttons.push(button);
+        }
+    }
+
+    pub fn unbind_button(&mut self, action: Action, button: GamepadButton) {
+        if let Some(buttons) = self.buttons.get_mut(&action) {
+            buttons.retain(|bound| *bound != button);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BindingsError {
+    Parse(DeJsonErr),
+    UnknownAction(String),
+    UnknownKey(String),
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingsError::Parse(err) => write!(f, "can't parse bindings: {}", err),
+            BindingsError::UnknownAction(name) => write!(f, "unknown action \"{}\"", name),
+            BindingsError::UnknownKey(name) => write!(f, "unknown key or button \"{}\"", name),
+        }
+    }
+}
+
+impl std::error::Error for BindingsError {}
+
+macro_rules! key_names {
+    ($($key:ident),* $(,)?) => {
+        pub fn key_code_from_name(name: &str) -> Option<KeyCode> {
+            match name {
+                $(stringify!($key) => Some(KeyCode::$key),)*
+                _ => None,
+            }
+        }
+
+        pub fn key_code_name(key: KeyCode) -> &'static str {
+            match key {
+                $(KeyCode::$key => stringify!($key),)*
+                KeyCode::Unknown => "Unknown",
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+key_names!(
+    Space, Apostrophe, Comma, Minus, Period, Slash, Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7,
+    Key8, Key9, Semicolon, Equal, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V,
+    W, X, Y, Z, LeftBracket, Backslash, RightBracket, GraveAccent, World1, World2, Escape, Enter,
+    Tab, Backspace, Insert, Delete, Right, Left, Down, Up, PageUp, PageDown, Home, End, CapsLock,
+    ScrollLock, NumLock, PrintScreen, Pause, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24, F25, Kp0, Kp1, Kp2, Kp3, Kp4, Kp5,
+    Kp6, Kp7, Kp8, Kp9, KpDecimal, KpDivide, KpMultiply, KpSubtract, KpAdd, KpEnter, KpEqual,
+    LeftShift, LeftControl, LeftAlt, LeftSuper, RightShift, RightControl, RightAlt, RightSuper,
+    Menu,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_override_listed_actions_only() {
+        let bindings = Bindings::from_json(r#"{ "Jump": ["Space", "W", "Up"] }"#).unwrap();
+        assert_eq!(
+            bindings.keys(Action::Jump),
+            &[KeyCode::Space, KeyCode::W, KeyCode::Up]
+        );
+        assert_eq!(bindings.keys(Action::MoveLeft), &[KeyCode::Left]);
+    }
+
+    #[test]
+    fn should_split_keys_and_buttons() {
+        let bindings = Bindings::from_json(r#"{ "Jump": ["Space", "South"] }"#).unwrap();
+        assert_eq!(bindings.keys(Action::Jump), &[KeyCode::Space]);
+        assert_eq!(bindings.buttons(Action::Jump), &[GamepadButton::South]);
+    }
+
+    #[test]
+    fn should_reject_unknown_key() {
+        let result = Bindings::from_json(r#"{ "Jump": ["Spacebar"] }"#);
+        assert!(matches!(result, Err(BindingsError::UnknownKey(name)) if name == "Spacebar"));
+    }
+
+    #[test]
+    fn should_reject_unknown_action() {
+        let result = Bindings::from_json(r#"{ "Fly": ["F"] }"#);
+        assert!(matches!(result, Err(BindingsError::UnknownAction(name)) if name == "Fly"));
+    }
+
+    #[test]
+    fn should_override_only_named_actions() {
+        let mut bindings = Bindings::from_json(r#"{ "Jump": ["Space"] }"#).unwrap();
+        let overrides = HashMap::from([("MoveLeft".to_string(), vec!["A".to_string()])]);
+        bindings.override_with(&overrides).unwrap();
+        assert_eq!(bindings.keys(Action::MoveLeft), &[KeyCode::A]);
+        assert_eq!(bindings.keys(Action::Jump), &[KeyCode::Space]);
+    }
+
+    #[test]
+    fn should_keep_bindings_when_any_override_is_invalid() {
+        let mut bindings = Bindings::default();
+        let overrides = HashMap::from([
+            ("MoveLeft".to_string(), vec!["A".to_string()]),
+            ("MoveRight".to_string(), vec!["D".to_string()]),
+            ("Jump".to_string(), vec!["Spacebar".to_string()]),
+        ]);
+        assert!(bindings.override_with(&overrides).is_err());
+        assert_eq!(bindings, Bindings::default());
+    }
+
+    #[test]
+    fn should_round_trip_key_names() {
+        assert_eq!(
+            key_code_from_name(key_code_name(KeyCode::KpEnter)),
+            Some(KeyCode::KpEnter)
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use macroquad::prelude::Vec2;
+
+/// Radial dead zone of the analog sticks, as a fraction of full deflection.
+pub const STICK_DEAD_ZONE: f32 = 0.2;
+/// Deflection past which a stick direction counts as a held digital action.
+pub const STICK_ACTION_THRESHOLD: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    pub const ALL: [GamepadButton; 10] = [
+        GamepadButton::South,
+        GamepadButton::East,
+        GamepadButton::North,
+        GamepadButton::West,
+        GamepadButton::Select,
+        GamepadButton::Start,
+        GamepadButton::DPadUp,
+        GamepadButton::DPadDown,
+        GamepadButton::DPadLeft,
+        GamepadButton::DPadRight,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GamepadButton::South => "South",
+            GamepadButton::East => "East",
+            GamepadButton::North => "North",
+            GamepadButton::West => "West",
+            GamepadButton::Select => "Select",
+            GamepadButton::Start => "Start",
+            GamepadButton::DPadUp => "DPadUp",
+            GamepadButton::DPadDown => "DPadDown",
+            GamepadButton::DPadLeft => "DPadLeft",
+            GamepadButton::DPadRight => "DPadRight",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|button| button.name() == name)
+    }
+}
+
+/// Source of gamepad state, polled once per frame by `InputService`.
+pub trait Gamepad {
+    fn update(&mut self);
+    fn is_button_down(&self, button: GamepadButton) -> bool;
+    /// Raw left stick deflection in `[-1, 1]`, with `y` pointing up.
+    fn left_stick(&self) -> Vec2;
+}
+
+/// Zeroes deflections inside the dead zone and rescales the rest back to `[0, 1]`.
+pub fn apply_dead_zone(stick: Vec2) -> Vec2 {
+    let magnitude = stick.length();
+    if magnitude < STICK_DEAD_ZONE {
+        return Vec2::ZERO;
+    }
+    let scaled = ((magnitude - STICK_DEAD_ZONE) / (1.0 - STICK_DEAD_ZONE)).min(1.0);
+    stick / magnitude * scaled
+}
+
+#[cfg(feature = "gamepad")]
+pub use self::gilrs_gamepad::GilrsGamepad;
+
+#[cfg(feature = "gamepad")]
+mod gilrs_gamepad {
+    use gilrs::{Axis, Button, Gilrs};
+    use macroquad::prelude::{vec2, Vec2};
+
+    use super::{Gamepad, GamepadButton};
+
+    /// Reads the first connected controller through gilrs.
+    pub struct GilrsGamepad {
+        gilrs: Gilrs,
+    }
+
+    impl GilrsGamepad {
+        /// Returns `None` when the platform has no gamepad support.
+        pub fn new() -> Option<Self> {
+            Gilrs::new().ok().map(|gilrs| Self { gilrs })
+        }
+
+        fn active(&self) -> Option<gilrs::Gamepad<'_>> {
+            self.gilrs.gamepads().map(|(_, gamepad)| gamepad).next()
+        }
+    }
+
+    impl Gamepad for GilrsGamepad {
+        fn update(&mut self) {
+            while self.gilrs.next_event().is_some() {}
+        }
+
+        fn is_button_down(&self, button: GamepadButton) -> bool {
+            self.active()
+                .is_some_and(|gamepad| gamepad.is_pressed(to_gilrs_button(button)))
+        }
+
+        fn left_stick(&self) -> Vec2 {
+            self.active().map_or(Vec2::ZERO, |gamepad| {
+                vec2(
+                    gamepad.value(Axis::LeftStickX),
+                    gamepad.value(Axis::LeftStickY),
+                )
+            })
+        }
+    }
+
+    fn to_gilrs_button(button: GamepadButton) -> Button {
+        match button {
+            GamepadButton::South => Button::South,
+            GamepadButton::East => Button::East,
+            GamepadButton::North => Button::North,
+            GamepadButton::West => Button::West,
+            GamepadButton::Select => Button::Select,
+            GamepadButton::Start => Button::Start,
+            GamepadButton::DPadUp => Button::DPadUp,
+            GamepadButton::DPadDown => Button::DPadDown,
+            GamepadButton::DPadLeft => Button::DPadLeft,
+            GamepadButton::DPadRight => Button::DPadRight,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::vec2;
+
+    use super::*;
+
+    #[test]
+    fn should_ignore_deflection_inside_dead_zone() {
+        assert_eq!(apply_dead_zone(vec2(0.1, -0.1)), Vec2::ZERO);
+    }
+
+    #[test]
+    fn should_rescale_deflection_outside_dead_zone() {
+        assert_eq!(apply_dead_zone(vec2(1.0, 0.0)), vec2(1.0, 0.0));
+        let half = STICK_DEAD_ZONE + (1.0 - STICK_DEAD_ZONE) / 2.0;
+        assert!((apply_dead_zone(vec2(-half, 0.0)).x + 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+
+pub use self::action::*;
+pub use self::bindings::*;
+pub use self::gamepad::*;
+
+mod action;
+mod bindings;
+mod gamepad;
+
+#[derive(Default)]
+pub struct InputService {
+    bindings: Bindings,
+    states: HashMap<Action, ActionState>,
+    gamepad: Option<Box<dyn Gamepad>>,
+    snapshot: InputSnapshot,
+}
+
+/// Everything `InputService` sampled from devices during one frame.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct InputSnapshot {
+    /// Actions held on keys and buttons; the stick's directions come from `left_stick`.
+    held: u8,
+    left_stick: Vec2,
+}
+
+// Every action needs a bit in `InputSnapshot::held`
+const _: () = assert!(Action::ALL.len() <= u8::BITS as usize);
+
+impl InputSnapshot {
+    pub fn new(held: u8, left_stick: Vec2) -> Self {
+        Self { held, left_stick }
+    }
+
+    /// Actions held on keys and buttons as a bit set indexed by `Action::index`.
+    pub fn held_bits(&self) -> u8 {
+        self.held
+    }
+
+    pub fn is_held(&self, action: Action) -> bool {
+        self.held & (1 << action.index()) != 0
+    }
+
+    pub fn set_held(&mut self, action: Action, held: bool) {
+        if held {
+            self.held |= 1 << action.index();
+        } else {
+            self.held &= !(1 << action.index());
+        }
+    }
+
+    /// Left stick deflection after the dead zone is applied.
+    pub fn left_stick(&self) -> Vec2 {
+        self.left_stick
+    }
+}
+
+impl InputService {
+    pub fn new(bindings: Bindings) -> Self {
+        Self {
+            bindings,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_gamepad(mut self, gamepad: Box<dyn Gamepad>) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Samples the keyboard and gamepad; call once per frame before reading actions.
+    pub fn update(&mut self) {
+        self.update_with(is_key_down);
+    }
+
+    pub fn update_with(&mut self, is_key_down: impl Fn(KeyCode) -> bool) {
+        if let Some(gamepad) = &mut self.gamepad {
+            gamepad.update();
+        }
+        let left_stick = self
+            .gamepad
+            .as_ref()
+            .map_or(Vec2::ZERO, |gamepad| apply_dead_zone(gamepad.left_stick()));
+
+        let mut snapshot = InputSnapshot::new(0, left_stick);
+        for action in Action::ALL {
+            let held = self
+                .bindings
+                .keys(action)
+                .iter()
+                .any(|key| is_key_down(*key))
+                || self.is_button_held(action);
+            snapshot.set_held(action, held);
+        }
+        self.apply(snapshot);
+    }
+
+    /// Advances action states from a snapshot instead of sampling devices, e.g. during replay.
+    pub fn apply(&mut self, snapshot: InputSnapshot) {
+        self.snapshot = snapshot;
+        for action in Action::ALL {
+            let held = snapshot.is_held(action) || is_stick_held(snapshot.left_stick(), action);
+            self.states.entry(action).or_default().update(held);
+        }
+    }
+
+    pub fn snapshot(&self) -> InputSnapshot {
+        self.snapshot
+    }
+
+    fn is_button_held(&self, action: Action) -> bool {
+        self.gamepad.as_ref().is_some_and(|gamepad| {
+            let buttons = self.bindings.buttons(action);
+            buttons.iter().any(|button| gamepad.is_button_down(*button))
+        })
+    }
+
+    pub fn is_held(&self, action: Action) -> bool {
+        self.state(action).is_held()
+    }
+
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.state(action).is_pressed()
+    }
+
+    pub fn is_released(&self, action: Action) -> bool {
+        self.state(action).is_released()
+    }
+
+    fn state(&self, action: Action) -> ActionState {
+        self.states.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Horizontal movement in `[-1, 1]`: keys and buttons while either direction is held on
+    /// them, otherwise the stick, so a drifting stick can't win over the d-pad.
+    pub fn horizontal_axis(&self) -> f32 {
+        let right = self.snapshot.is_held(Action::MoveRight);
+        let left = self.snapshot.is_held(Action::MoveLeft);
+        if right || left {
+            return right as i32 as f32 - left as i32 as f32;
+        }
+        self.snapshot.left_stick().x
+    }
+
+    pub fn bindings(&self) -> &Bindings {
+        &self.bindings
+    }
+
+    pub fn bindings_mut(&mut self) -> &mut Bindings {
+        &mut self.bindings
+    }
+}
+
+fn is_stick_held(left_stick: Vec2, action: Action) -> bool {
+    match action {
+        Action::MoveLeft => left_stick.x <= -STICK_ACTION_THRESHOLD,
+        Action::MoveRight => left_stick.x >= STICK_ACTION_THRESHOLD,
+        Action::Descend => left_stick.y <= -STICK_ACTION_THRESHOLD,
+        Action::MenuUp => left_stick.y >= STICK_ACTION_THRESHOLD,
+        Action::MenuDown => left_stick.y <= -STICK_ACTION_THRESHOLD,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_track_pressed_held_released() {
+        let mut input = InputService::default();
+
+        input.update_with(|key| key == KeyCode::Space);
+        assert!(input.is_pressed(Action::Jump) && input.is_held(Action::Jump));
+
+        input.update_with(|key| key == KeyCode::Space);
+        assert!(!input.is_pressed(Action::Jump) && input.is_held(Action::Jump));
+
+        input.update_with(|_| false);
+        assert!(input.is_released(Action::Jump) && !input.is_held(Action::Jump));
+    }
+
+    #[test]
+    fn should_hold_action_with_any_bound_key() {
+        let mut bindings = Bindings::default();
+        bindings.bind(Action::MoveLeft, KeyCode::A);
+        let mut input = InputService::new(bindings);
+
+        input.update_with(|key| key == KeyCode::A);
+        assert!(input.is_held(Action::MoveLeft));
+    }
+
+    #[test]
+    fn should_replay_applied_snapshot() {
+        let mut recorded = InputService::default();
+        recorded.update_with(|key| key == KeyCode::Space || key == KeyCode::Left);
+
+        let mut replayed = InputService::default();
+        replayed.apply(recorded.snapshot());
+        assert!(replayed.is_pressed(Action::Jump));
+        assert_eq!(replayed.horizontal_axis(), -1.0);
+    }
+
+    #[derive(Default)]
+    struct FakeGamepad {
+        buttons: Vec<GamepadButton>,
+        left_stick: Vec2,
+    }
+
+    impl Gamepad for FakeGamepad {
+        fn update(&mut self) {}
+
+        fn is_button_down(&self, button: GamepadButton) -> bool {
+            self.buttons.contains(&button)
+        }
+
+        fn left_stick(&self) -> Vec2 {
+            self.left_stick
+        }
+    }
+
+    fn input_with_gamepad(gamepad: FakeGamepad) -> InputService {
+        let mut input = InputService::default().with_gamepad(Box::new(gamepad));
+        input.update_with(|_| false);
+        input
+    }
+
+    #[test]
+    fn should_press_jump_with_south_button() {
+        let input = input_with_gamepad(FakeGamepad {
+            buttons: vec![GamepadButton::South],
+            ..Default::default()
+        });
+        assert!(input.is_pressed(Action::Jump));
+    }
+
+    #[test]
+    fn should_descend_with_stick_down() {
+        let input = input_with_gamepad(FakeGamepad {
+            left_stick: vec2(0.0, -0.9),
+            ..Default::default()
+        });
+        assert!(input.is_held(Action::Descend));
+        assert_eq!(input.horizontal_axis(), 0.0);
+    }
+
+    #[test]
+    fn should_map_stick_magnitude_to_horizontal_axis() {
+        let input = input_with_gamepad(FakeGamepad {
+            left_stick: vec2(0.6, 0.0),
+            ..Default::default()
+        });
+        assert!((input.horizontal_axis() - 0.5).abs() < 1e-6);
+        assert!(input.is_held(Action::MoveRight));
+    }
+
+    #[test]
+    fn should_prefer_digital_input_inside_dead_zone() {
+        let input = input_with_gamepad(FakeGamepad {
+            left_stick: vec2(-0.15, 0.0),
+            buttons: vec![GamepadButton::DPadRight],
+        });
+        assert_eq!(input.horizontal_axis(), 1.0);
+    }
+
+    #[test]
+    fn should_prefer_digital_input_over_stick_drift() {
+        let input = input_with_gamepad(FakeGamepad {
+            left_stick: vec2(-0.4, 0.0),
+            buttons: vec![GamepadButton::DPadRight],
+        });
+        assert_eq!(input.horizontal_axis(), 1.0);
+    }
+}
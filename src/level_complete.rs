@@ -0,0 +1,135 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use macroquad::prelude::*;
+
+use crate::campaign::SharedCampaign;
+use crate::collectibles::Tally;
+use crate::game::Game;
+use crate::input_service::{Action, InputService};
+use crate::stage::{Stage, Transition};
+use crate::title_screen::draw_centered_text;
+
+const TITLE_FONT_SIZE: f32 = 64.0;
+const FONT_SIZE: f32 = 40.0;
+const LINE_HEIGHT: f32 = 56.0;
+
+/// What the player did in a finished level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelSummary {
+    pub time: Duration,
+    pub tallies: Vec<Tally>,
+}
+
+/// Shown between levels; confirming moves on to the next one, or back once the campaign is over.
+pub struct LevelComplete {
+    campaign: SharedCampaign,
+    level_index: usize,
+    summary: LevelSummary,
+}
+
+impl LevelComplete {
+    pub fn new(campaign: SharedCampaign, level_index: usize, summary: LevelSummary) -> Self {
+        Self {
+            campaign,
+            level_index,
+            summary,
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("Time {:.2}s", self.summary.time.as_secs_f32())];
+        lines.extend(self.summary.tallies.iter().map(Tally::to_string));
+        lines
+    }
+}
+
+#[async_trait(?Send)]
+impl Stage for LevelComplete {
+    fn tick(&mut self, input: &InputService, _frame_time: Duration) -> Transition {
+        if input.is_pressed(Action::Pause) {
+            return Transition::Pop;
+        }
+        if !input.is_pressed(Action::Confirm) {
+            return Transition::None;
+        }
+        let next = self.campaign.borrow().next_level(self.level_index);
+        match next {
+            Some(next) => Transition::Replace(Box::new(Game::new(Rc::clone(&self.campaign), next))),
+            None => Transition::Pop,
+        }
+    }
+
+    fn draw(&mut self) {
+        set_default_camera();
+        clear_background(SKYBLUE);
+
+        let name = self
+            .campaign
+            .borrow()
+            .level(self.level_index)
+            .map_or_else(String::new, |level| level.name.clone());
+        let lines = self.lines();
+        let top = (screen_height() - lines.len() as f32 * LINE_HEIGHT) / 2.0;
+        draw_centered_text(
+            &format!("{} complete", name),
+            top - TITLE_FONT_SIZE,
+            TITLE_FONT_SIZE,
+        );
+        for (i, line) in lines.iter().enumerate() {
+            draw_centered_text(line, top + i as f32 * LINE_HEIGHT, FONT_SIZE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use macroquad::prelude::KeyCode;
+
+    use super::*;
+    use crate::campaign::{Campaign, LevelInfo};
+    use crate::collectibles::CollectibleKind;
+
+    fn press(screen: &mut LevelComplete, key: KeyCode) -> Transition {
+        let mut input = InputService::default();
+        input.update_with(|pressed| pressed == key);
+        screen.tick(&input, Duration::ZERO)
+    }
+
+    fn screen(level_index: usize) -> LevelComplete {
+        let level = |name: &str| LevelInfo {
+            name: name.into(),
+            map: format!("{}.json", name),
+        };
+        let campaign = Campaign::new(vec![level("a"), level("b")]);
+        let summary = LevelSummary {
+            time: Duration::from_millis(12_340),
+            tallies: vec![Tally {
+                kind: CollectibleKind::Coin,
+                collected: 3,
+                total: 5,
+            }],
+        };
+        LevelComplete::new(Rc::new(RefCell::new(campaign)), level_index, summary)
+    }
+
+    #[test]
+    fn should_list_time_and_tallies() {
+        assert_eq!(screen(0).lines(), ["Time 12.34s", "coin 3/5"]);
+    }
+
+    #[test]
+    fn should_continue_to_next_level_or_back() {
+        assert!(matches!(
+            press(&mut screen(0), KeyCode::Enter),
+            Transition::Replace(_)
+        ));
+        assert!(matches!(
+            press(&mut screen(1), KeyCode::Enter),
+            Transition::Pop
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use macroquad::prelude::*;
+
+use crate::campaign::SharedCampaign;
+use crate::game::Game;
+use crate::input_service::{Action, InputService};
+use crate::stage::{Stage, Transition};
+use crate::title_screen::draw_centered_text;
+
+const TITLE: &str = "Select level";
+const TITLE_FONT_SIZE: f32 = 64.0;
+const FONT_SIZE: f32 = 40.0;
+const LINE_HEIGHT: f32 = 56.0;
+
+/// Lists the campaign's levels; locked ones are shown but can't be started.
+pub struct LevelSelect {
+    campaign: SharedCampaign,
+    selected: usize,
+}
+
+impl LevelSelect {
+    pub fn new(campaign: SharedCampaign) -> Self {
+        Self {
+            campaign,
+            selected: 0,
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    fn label(&self, index: usize) -> String {
+        let campaign = self.campaign.borrow();
+        let name = &campaign.levels()[index].name;
+        let label = if let Some(time) = campaign.best_time(index) {
+            format!("{} - {:.2}s", name, time.as_secs_f32())
+        } else if campaign.is_completed(index) {
+            format!("{} - done", name)
+        } else if campaign.is_unlocked(index) {
+            name.clone()
+        } else {
+            format!("{} - locked", name)
+        };
+        if index == self.selected {
+            format!("> {} <", label)
+        } else {
+            label
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Stage for LevelSelect {
+    fn tick(&mut self, input: &InputService, _frame_time: Duration) -> Transition {
+        let len = self.campaign.borrow().levels().len();
+        if len == 0 {
+            return if input.is_pressed(Action::Pause) {
+                Transition::Pop
+            } else {
+                Transition::None
+            };
+        }
+
+        if input.is_pressed(Action::MenuUp) {
+            self.selected = (self.selected + len - 1) % len;
+        }
+        if input.is_pressed(Action::MenuDown) {
+            self.selected = (self.selected + 1) % len;
+        }
+
+        if input.is_pressed(Action::Pause) {
+            Transition::Pop
+        } else if input.is_pressed(Action::Confirm)
+            && self.campaign.borrow().is_unlocked(self.selected)
+        {
+            let game = Game::new(Rc::clone(&self.campaign), self.selected);
+            Transition::Push(Box::new(game))
+        } else {
+            Transition::None
+        }
+    }
+
+    fn draw(&mut self) {
+        set_default_camera();
+        clear_background(SKYBLUE);
+
+        let len = self.campaign.borrow().levels().len();
+        let top = (screen_height() - len as f32 * LINE_HEIGHT) / 2.0;
+        draw_centered_text(TITLE, top - TITLE_FONT_SIZE, TITLE_FONT_SIZE);
+        for i in 0..len {
+            draw_centered_text(&self.label(i), top + i as f32 * LINE_HEIGHT, FONT_SIZE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use macroquad::prelude::KeyCode;
+
+    use super::*;
+    use crate::campaign::{Campaign, LevelInfo};
+
+    fn press(screen: &mut LevelSelect, key: KeyCode) -> Transition {
+        let mut input = InputService::default();
+        input.update_with(|pressed| pressed == key);
+        screen.tick(&input, Duration::ZERO)
+    }
+
+    fn two_levels() -> SharedCampaign {
+        let level = |name: &str| LevelInfo {
+            name: name.into(),
+            map: format!("{}.json", name),
+        };
+        Rc::new(RefCell::new(Campaign::new(vec![level("a"), level("b")])))
+    }
+
+    #[test]
+    fn should_not_start_locked_level() {
+        let mut screen = LevelSelect::new(two_levels());
+        press(&mut screen, KeyCode::Down);
+        assert_eq!(screen.selected(), 1);
+        assert!(matches!(
+            press(&mut screen, KeyCode::Enter),
+            Transition::None
+        ));
+    }
+
+    #[test]
+    fn should_start_unlocked_level() {
+        let campaign = two_levels();
+        campaign.borrow_mut().complete(0);
+        let mut screen = LevelSelect::new(campaign);
+        press(&mut screen, KeyCode::Down);
+        assert!(matches!(
+            press(&mut screen, KeyCode::Enter),
+            Transition::Push(_)
+        ));
+    }
+}
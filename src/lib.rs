@@ -0,0 +1,33 @@
+pub mod animation;
+pub mod assets_server;
+pub mod camera;
+pub mod campaign;
+pub mod checkpoints;
+pub mod collectibles;
+pub mod duck;
+pub mod duck_animation;
+pub mod duck_params;
+pub mod duck_world;
+pub mod enemies;
+pub mod error_screen;
+pub mod fixed_timestep;
+pub mod game;
+pub mod game_over;
+pub mod hazards;
+pub mod input_service;
+pub mod level_complete;
+pub mod level_select;
+pub mod moving_platforms;
+pub mod pause_menu;
+pub mod physics;
+pub mod replay;
+pub mod save;
+pub mod simulation;
+pub mod slopes;
+pub mod stage;
+pub mod surfaces;
+#[cfg(test)]
+pub mod test_worlds;
+pub mod tiled_map;
+pub mod title_screen;
+pub mod water;
@@ -1,141 +1,219 @@
-mod assets_server;
-mod camera;
-mod duck;
-mod duck_world;
-mod input_service;
-mod physics;
-mod tiled_map;
-
-use async_trait::async_trait;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
 use macroquad::prelude::*;
-use macroquad_platformer::*;
 
-use crate::assets_server::AssetsServer;
-use crate::camera::Camera;
-use crate::duck::Duck;
-use crate::duck_world::DuckWorld;
-use crate::input_service::InputService;
-use crate::tiled_map::TiledMap;
+use duck_game::assets_server::{AssetError, AssetsServer};
+use duck_game::campaign::{Campaign, CAMPAIGN_PATH};
+use duck_game::error_screen::ErrorScreen;
+use duck_game::game::load_duck_params;
+use duck_game::input_service::{Bindings, InputService};
+use duck_game::replay::{Replay, ReplayPlayer};
+use duck_game::save::{SaveData, SaveStore, WindowMode};
+use duck_game::stage::{StageManager, Transition};
+use duck_game::title_screen::TitleScreen;
+
+const BINDINGS_PATH: &str = "bindings.json";
+
+mod replay_keys {
+    use macroquad::prelude::KeyCode;
+
+    pub const QUIT: KeyCode = KeyCode::Escape;
+    pub const PAUSE: KeyCode = KeyCode::P;
+    pub const STEP: KeyCode = KeyCode::Period;
+    pub const FAST_FORWARD: KeyCode = KeyCode::F;
+}
 
-const VIEWPORT_HEIGHT: f32 = 720.0;
+/// Where frame input comes from, chosen by `--record <path>` or `--replay <path>`.
+enum InputMode {
+    Live,
+    Record { replay: Replay, path: String },
+    Replay(ReplayPlayer),
+}
 
-mod tile_layers {
-    pub const BORDERS: &str = "Tile Layer 2";
-    pub const BARRIERS: &str = "Tile Layer 3";
+impl InputMode {
+    /// Recording pins the duck params it starts with; a replay swaps in the progress and duck
+    /// params it was recorded with, so it plays out the same on any machine.
+    async fn from_args(campaign: &mut Campaign, assets_server: &AssetsServer) -> Self {
+        let args = std::env::args().collect::<Vec<_>>();
+        match args.get(1..3) {
+            Some([flag, path]) if flag == "--record" => {
+                match load_duck_params(assets_server).await {
+                    Ok(duck_params) => {
+                        campaign.pin_duck_params(duck_params);
+                        Self::Record {
+                            replay: Replay::new(campaign.progress(), duck_params),
+                            path: path.clone(),
+                        }
+                    }
+                    Err(err) => {
+                        error!("Playing without recording to {}: {}", path, err);
+                        Self::Live
+                    }
+                }
+            }
+            Some([flag, path]) if flag == "--replay" => match Replay::load(path) {
+                Ok(replay) => {
+                    campaign.apply_progress(replay.progress());
+                    campaign.pin_duck_params(replay.duck_params());
+                    Self::Replay(ReplayPlayer::new(replay))
+                }
+                Err(err) => {
+                    error!("Playing live instead of {}: {}", path, err);
+                    Self::Live
+                }
+            },
+            _ => Self::Live,
+        }
+    }
 }
 
-#[macroquad::main("Уточка")]
-async fn main() {
-    let input_service = InputService::default();
+fn window_conf() -> Conf {
+    // Read quietly here: `main` loads the save again and reports what's wrong with it
+    let window_mode = SaveStore::in_config_dir()
+        .and_then(|save_store| save_store.load().ok())
+        .map(|save_data| save_data.settings.window_mode)
+        .unwrap_or(WindowMode::Windowed);
+    Conf {
+        window_title: "Уточка".to_owned(),
+        fullscreen: window_mode == WindowMode::Fullscreen,
+        ..Default::default()
+    }
+}
 
-    let mut game: Box<dyn Stage> = Box::new(Game::default());
+#[macroquad::main(window_conf)]
+async fn main() {
+    let assets_server = AssetsServer::new("assets");
+    let (save_store, mut save_data) = load_save();
+    let bindings = load_bindings(&assets_server, &save_data).await;
+    let campaign = load_campaign(&assets_server).await;
+    let mut input_service = InputService::new(bindings.as_ref().cloned().unwrap_or_default());
+    #[cfg(feature = "gamepad")]
+    if let Some(gamepad) = duck_game::input_service::GilrsGamepad::new() {
+        input_service = input_service.with_gamepad(Box::new(gamepad));
+    }
 
-    game.load().await;
+    let mut stages = StageManager::default();
+
+    let (mut campaign, campaign_error) = match campaign {
+        Ok(campaign) => (campaign, None),
+        Err(err) => (Campaign::default(), Some(err)),
+    };
+    campaign.apply_progress(&save_data.progress);
+    let mut input_mode = InputMode::from_args(&mut campaign, &assets_server).await;
+    let campaign = Rc::new(RefCell::new(campaign));
+    let title_screen = TitleScreen::new(Rc::clone(&campaign));
+    stages.push(Box::new(title_screen)).await;
+    // Default bindings and an empty campaign still let the player leave the error screens,
+    // one per broken file
+    for err in [bindings.err(), campaign_error].into_iter().flatten() {
+        stages.push(Box::new(ErrorScreen::new(err))).await;
+    }
 
     loop {
-        if input_service.is_key_pressed(KeyCode::Escape) {
+        let transition = match &mut input_mode {
+            InputMode::Replay(player) => {
+                if is_key_pressed(replay_keys::QUIT) {
+                    stages.clear();
+                    return;
+                }
+                handle_replay_controls(player);
+                let mut transition = Transition::None;
+                for frame in player.next_frames() {
+                    input_service.apply(frame.snapshot());
+                    transition = stages.tick(&input_service, frame.frame_time());
+                    if !matches!(transition, Transition::None) {
+                        break;
+                    }
+                }
+                transition
+            }
+            InputMode::Live | InputMode::Record { .. } => {
+                input_service.update();
+                let frame_time = Duration::from_secs_f32(get_frame_time());
+                if let InputMode::Record { replay, .. } = &mut input_mode {
+                    replay.record(input_service.snapshot(), frame_time);
+                }
+                stages.tick(&input_service, frame_time)
+            }
+        };
+
+        // Watching a replay must not touch the player's own progress. Nothing in the game edits
+        // settings yet, so they're written back unchanged along with it.
+        if !matches!(input_mode, InputMode::Replay(_)) && campaign.borrow_mut().take_changed() {
+            save_data.progress = campaign.borrow().progress();
+            write_save(save_store.as_ref(), &save_data);
+        }
+
+        if !stages.apply(transition).await {
+            if let InputMode::Record { replay, path } = &input_mode {
+                if let Err(err) = replay.save(path) {
+                    error!("Replay not saved to {}: {}", path, err);
+                }
+            }
             return;
         }
 
-        game.tick();
+        stages.draw();
 
         next_frame().await;
     }
 }
 
-async fn load_map(assets_server: &AssetsServer) -> TiledMap {
-    let tiled_map_json = assets_server.load_string("map.json").await.unwrap();
-
-    let tileset_json = assets_server
-        .load_string("tmw_desert_spacing.json")
-        .await
-        .unwrap();
-
-    let tileset_png = assets_server
-        .load_texture("tmw_desert_spacing.png")
-        .await
-        .unwrap();
-
-    let macroquad_map = macroquad_tiled::load_map(
-        &tiled_map_json,
-        &[("tmw_desert_spacing.png", tileset_png)],
-        &[("tmw_desert_spacing.json", &tileset_json)],
-    )
-    .unwrap();
-
-    TiledMap::new(macroquad_map)
-}
-
-async fn load_duck_texture(assets_server: &AssetsServer) -> Texture2D {
-    assets_server.load_texture("duck.png").await.unwrap()
-}
-
-#[async_trait]
-trait Stage {
-    async fn load(&mut self);
-    fn tick(&mut self);
-    fn destroy(self);
+fn handle_replay_controls(player: &mut ReplayPlayer) {
+    if is_key_pressed(replay_keys::PAUSE) {
+        player.toggle_pause();
+    }
+    if is_key_pressed(replay_keys::STEP) {
+        player.step();
+    }
+    player.set_fast_forward(is_key_down(replay_keys::FAST_FORWARD));
 }
 
-enum Game {
-    Unloaded,
-    Loaded {
-        world: Box<DuckWorld>,
-        duck: Duck,
-        camera: Camera,
-    },
+async fn load_campaign(assets_server: &AssetsServer) -> Result<Campaign, AssetError> {
+    let campaign_ron = assets_server.load_string(CAMPAIGN_PATH).await?;
+    Campaign::from_ron(&campaign_ron).map_err(|err| AssetError::parse(CAMPAIGN_PATH, err))
 }
 
-impl Default for Game {
-    fn default() -> Self {
-        Self::Unloaded
+/// A failed write is only logged; the next progress change tries again.
+fn write_save(save_store: Option<&SaveStore>, save_data: &SaveData) {
+    let Some(save_store) = save_store else {
+        return;
+    };
+    if let Err(err) = save_store.save(save_data) {
+        error!(
+            "Save not written to {}: {}",
+            save_store.path().display(),
+            err
+        );
     }
 }
 
-#[async_trait]
-impl Stage for Game {
-    async fn load(&mut self) {
-        let assets_server = AssetsServer::new("assets");
-
-        let tiled_map = load_map(&assets_server).await;
-        let map_size = tiled_map.size();
-        let aspect_ratio = screen_width() / screen_height();
-        let viewport_size = vec2(aspect_ratio * VIEWPORT_HEIGHT, VIEWPORT_HEIGHT);
-
-        let mut world = DuckWorld::new(tiled_map);
-        let duck_texture = load_duck_texture(&assets_server).await;
-
-        world.add_static_colliders(tile_layers::BORDERS, Tile::Solid);
-        world.add_static_colliders(tile_layers::BARRIERS, Tile::JumpThrough);
-
-        let duck = Duck::create(duck_texture, &mut world, vec2(50_f32, map_size.y - 150_f32));
-        let camera = Camera::new(map_size, viewport_size);
-
-        *self = Self::Loaded {
-            world: Box::new(world),
-            duck,
-            camera,
-        };
-    }
-
-    fn tick(&mut self) {
-        if let Self::Loaded {
-            world,
-            duck,
-            camera,
-        } = self
-        {
-            duck.update(world);
-            camera.update(duck.center(world));
-
-            world.draw(camera.viewport());
-            duck.draw(world);
-
-            camera.focus();
-        } else {
-            panic!("Can't call tick before loading")
+/// A save that can't be read is left untouched on disk: the session starts fresh and saves nothing.
+fn load_save() -> (Option<SaveStore>, SaveData) {
+    let Some(save_store) = SaveStore::in_config_dir() else {
+        warn!("No config directory, progress won't be saved");
+        return (None, SaveData::default());
+    };
+    match save_store.load() {
+        Ok(save_data) => (Some(save_store), save_data),
+        Err(err) => {
+            error!("Ignoring {}: {}", save_store.path().display(), err);
+            (None, SaveData::default())
         }
     }
+}
 
-    fn destroy(self) {}
+async fn load_bindings(
+    assets_server: &AssetsServer,
+    save_data: &SaveData,
+) -> Result<Bindings, AssetError> {
+    let bindings_json = assets_server.load_string(BINDINGS_PATH).await?;
+    let mut bindings =
+        Bindings::from_json(&bindings_json).map_err(|err| AssetError::parse(BINDINGS_PATH, err))?;
+    if let Err(err) = bindings.override_with(&save_data.settings.bindings) {
+        error!("Ignoring saved key bindings: {}", err);
+    }
+    Ok(bindings)
 }
@@ -0,0 +1,309 @@
+use std::time::Duration;
+
+use macroquad::prelude::*;
+use macroquad_platformer::{Solid, World};
+
+use crate::assets_server::AssetError;
+use crate::duck::Duck;
+use crate::physics::{Velocity, WORLD_SCALE};
+use crate::tiled_map::{MapObject, TiledMap};
+
+/// Name of the Tiled polylines that moving platforms travel along, back and forth.
+pub const PLATFORM: &str = "platform";
+
+/// Properties of a platform polyline.
+mod platform_property {
+    /// Travel speed in meters per second; required.
+    pub const SPEED: &str = "speed";
+    /// Platform size in pixels; the path moves its top-left corner.
+    pub const WIDTH: &str = "width";
+    pub const HEIGHT: &str = "height";
+    /// `true` to let the duck jump up through it and drop down with Down+Space.
+    pub const JUMP_THROUGH: &str = "jumpthrough";
+}
+
+const DEFAULT_WIDTH: f32 = 96.0;
+const DEFAULT_HEIGHT: f32 = 32.0;
+/// How far below its path a jump-through platform's solid waits while it lets the duck pass:
+/// macroquad_platformer solids block from every side and keep their collidability to themselves,
+/// so moving the solid out of the way is the only way to turn one off. Parking it straight down
+/// keeps `solid_move` from dragging riders sideways on the way.
+const PARKED_DEPTH: f32 = 100_000.0;
+
+/// A moving platform as described in the map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlatformSpec {
+    pub path: Vec<Vec2>,
+    pub size: Vec2,
+    pub speed: Velocity,
+    pub jump_through: bool,
+}
+
+impl PlatformSpec {
+    pub fn from_map(tiled_map: &TiledMap, map_path: &str) -> Result<Vec<Self>, AssetError> {
+        tiled_map
+            .objects()
+            .iter()
+            .filter(|object| object.name == PLATFORM)
+            .map(|object| Self::from_object(object, map_path))
+            .collect()
+    }
+
+    fn from_object(object: &MapObject, map_path: &str) -> Result<Self, AssetError> {
+        let invalid = |cause: String| AssetError::InvalidProperty {
+            path: map_path.into(),
+            cause: format!("platform at {}: {}", object.rect.point(), cause),
+        };
+        if object.polyline.len() < 2 {
+            return Err(invalid(
+                "needs a polyline path with at least 2 points".into(),
+            ));
+        }
+        let number = |name: &str, default: Option<f32>| match object.properties.get(name) {
+            Some(value) => value
+                .as_f32()
+                .filter(|value| *value > 0.0)
+                .ok_or_else(|| invalid(format!("{}={} must be a positive number", name, value))),
+            None => default.ok_or_else(|| invalid(format!("{} is missing", name))),
+        };
+        let jump_through = match object.properties.get(platform_property::JUMP_THROUGH) {
+            Some(value) => value.as_bool().ok_or_else(|| {
+                invalid(format!(
+                    "{}={} must be true or false",
+                    platform_property::JUMP_THROUGH,
+                    value
+                ))
+            })?,
+            None => false,
+        };
+        Ok(Self {
+            path: object.polyline.clone(),
+            size: vec2(
+                number(platform_property::WIDTH, Some(DEFAULT_WIDTH))?,
+                number(platform_property::HEIGHT, Some(DEFAULT_HEIGHT))?,
+            ),
+            speed: Velocity::from_meters_on_second(number(platform_property::SPEED, None)?),
+            jump_through,
+        })
+    }
+}
+
+/// Walks a polyline from the first point to the last and back again.
+struct PlatformPath {
+    points: Vec<Vec2>,
+    target: usize,
+    forward: bool,
+}
+
+impl PlatformPath {
+    fn new(points: Vec<Vec2>) -> Self {
+        Self {
+            points,
+            target: 1,
+            forward: true,
+        }
+    }
+
+    fn advance(&mut self, mut pos: Vec2, mut distance: f32) -> Vec2 {
+        // Bounded so a path of identical points can't spin forever
+        for _ in 0..self.points.len() * 2 {
+            let to_target = self.points[self.target] - pos;
+            let length = to_target.length();
+            if length > distance {
+                return pos + to_target / length * distance;
+            }
+            pos = self.points[self.target];
+            distance -= length;
+            self.turn();
+        }
+        pos
+    }
+
+    fn start(&self) -> Vec2 {
+        self.points[0]
+    }
+
+    fn reset(&mut self) {
+        self.target = 1;
+        self.forward = true;
+    }
+
+    fn turn(&mut self) {
+        let last = self.points.len() - 1;
+        if self.forward && self.target == last || !self.forward && self.target == 0 {
+            self.forward = !self.forward;
+        }
+        if self.forward {
+            self.target += 1;
+        } else {
+            self.target -= 1;
+        }
+    }
+}
+
+pub struct MovingPlatform {
+    solid: Solid,
+    size: Vec2,
+    pos: Vec2,
+    previous_pos: Vec2,
+    path: PlatformPath,
+    speed: Velocity,
+    jump_through: bool,
+    /// Whether the solid is in place; jump-through platforms park it while the duck passes.
+    active: bool,
+}
+
+impl MovingPlatform {
+    pub fn new(world: &mut World, spec: PlatformSpec) -> Self {
+        let pos = spec.path[0];
+        let active = !spec.jump_through;
+        let solid_pos = if active { pos } else { parked(pos) };
+        Self {
+            solid: world.add_solid(solid_pos.round(), spec.size.x as _, spec.size.y as _),
+            size: spec.size,
+            pos,
+            previous_pos: pos,
+            path: PlatformPath::new(spec.path),
+            speed: spec.speed,
+            jump_through: spec.jump_through,
+            active,
+        }
+    }
+
+    /// Bounds of the solid when it's in place.
+    pub fn rect(&self) -> Rect {
+        let pos = self.pos.round();
+        Rect::new(pos.x, pos.y, self.size.x, self.size.y)
+    }
+
+    /// Moves the platform back to the start of its path; riders are left where they are.
+    fn reset(&mut self, world: &mut World) {
+        self.path.reset();
+        self.pos = self.path.start();
+        self.previous_pos = self.pos;
+        self.active = !self.jump_through;
+        self.move_solid(world);
+    }
+
+    fn step(&mut self, world: &mut World, duck: &Duck, frame_time: Duration) {
+        let duck_rect = duck.rect(world);
+        let top = self.rect().top();
+        // Touching rects count as overlapping, so an actor that lands on a solid rests 1px above
+        let is_ridden = self.active
+            && (top - 1.0..=top).contains(&duck_rect.bottom())
+            && duck_rect.right() > self.rect().left()
+            && duck_rect.left() < self.rect().right();
+
+        self.previous_pos = self.pos;
+        let distance = WORLD_SCALE.to_pixels(self.speed * frame_time);
+        self.pos = self.path.advance(self.pos, distance);
+        if self.jump_through {
+            // Only solid under a duck that is fully above it, so the solid never lands inside it
+            let is_above = duck_rect.bottom() <= self.rect().top();
+            self.active = !duck.is_descending() && (is_ridden || is_above);
+        }
+
+        // The duck moves before a rising solid and after a sinking one, so it never runs into it;
+        // `move_v` collides, so a rising platform can't carry the duck into a ceiling
+        let delta = self.solid_delta(world);
+        let carries_duck = is_ridden && self.active;
+        if carries_duck && delta.y < 0.0 {
+            world.move_v(duck.actor(), delta.y);
+        }
+        self.move_solid(world);
+        if carries_duck && delta.y > 0.0 {
+            world.move_v(duck.actor(), delta.y);
+        }
+    }
+
+    /// How far the solid has to go to be where the platform is, or parked.
+    fn solid_delta(&self, world: &World) -> Vec2 {
+        let target = if self.active {
+            self.pos.round()
+        } else {
+            parked(self.pos.round())
+        };
+        target - world.solid_pos(self.solid)
+    }
+
+    fn move_solid(&mut self, world: &mut World) {
+        let delta = self.solid_delta(world);
+        // Carries riders sideways but leaves their height alone
+        world.solid_move(self.solid, delta.x, delta.y);
+    }
+
+    fn draw(&self, alpha: f32, viewport: Rect) {
+        let pos = self.previous_pos.lerp(self.pos, alpha);
+        let rect = Rect::new(pos.x, pos.y, self.size.x, self.size.y);
+        if !rect.overlaps(&viewport) {
+            return;
+        }
+        let color = if self.jump_through { BEIGE } else { BROWN };
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, color);
+        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2.0, DARKBROWN);
+    }
+}
+
+fn parked(pos: Vec2) -> Vec2 {
+    pos + vec2(0.0, PARKED_DEPTH)
+}
+
+/// Steps every moving platform after the duck, so riders move along in the same step.
+#[derive(Default)]
+pub struct MovingPlatforms {
+    platforms: Vec<MovingPlatform>,
+}
+
+impl MovingPlatforms {
+    pub fn new(world: &mut World, specs: Vec<PlatformSpec>) -> Self {
+        let platforms = specs
+            .into_iter()
+            .map(|spec| MovingPlatform::new(world, spec))
+            .collect();
+        Self { platforms }
+    }
+
+    pub fn platforms(&self) -> &[MovingPlatform] {
+        &self.platforms
+    }
+
+    pub fn reset(&mut self, world: &mut World) {
+        for platform in &mut self.platforms {
+            platform.reset(world);
+        }
+    }
+
+    pub fn step(&mut self, world: &mut World, duck: &Duck, frame_time: Duration) {
+        for platform in &mut self.platforms {
+            platform.step(world, duck, frame_time);
+        }
+    }
+
+    pub fn draw(&self, alpha: f32, viewport: Rect) {
+        for platform in &self.platforms {
+            platform.draw(alpha, viewport);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_walk_path_back_and_forth() {
+        let mut path = PlatformPath::new(vec![vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(10.0, 10.0)]);
+        let pos = path.advance(vec2(0.0, 0.0), 15.0);
+        assert_eq!(pos, vec2(10.0, 5.0));
+        let pos = path.advance(pos, 10.0);
+        assert_eq!(pos, vec2(10.0, 5.0));
+        let pos = path.advance(pos, 20.0);
+        assert_eq!(pos, vec2(5.0, 0.0));
+    }
+
+    #[test]
+    fn should_not_hang_on_zero_length_path() {
+        let mut path = PlatformPath::new(vec![vec2(1.0, 1.0), vec2(1.0, 1.0)]);
+        assert_eq!(path.advance(vec2(1.0, 1.0), 5.0), vec2(1.0, 1.0));
+    }
+}
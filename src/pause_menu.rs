@@ -0,0 +1,150 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use macroquad::prelude::*;
+
+use crate::input_service::{Action, InputService};
+use crate::stage::{Stage, Transition};
+use crate::title_screen::draw_centered_text;
+
+const FONT_SIZE: f32 = 48.0;
+const LINE_HEIGHT: f32 = 64.0;
+const BACKDROP: Color = Color::new(0.0, 0.0, 0.0, 0.6);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseMenuItem {
+    Resume,
+    Restart,
+    Quit,
+}
+
+impl PauseMenuItem {
+    pub const ALL: [PauseMenuItem; 3] = [
+        PauseMenuItem::Resume,
+        PauseMenuItem::Restart,
+        PauseMenuItem::Quit,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            PauseMenuItem::Resume => "Resume",
+            PauseMenuItem::Restart => "Restart level",
+            PauseMenuItem::Quit => "Quit",
+        }
+    }
+}
+
+/// Overlay drawn over the frozen level; the chosen item is reported through `choice`.
+pub struct PauseMenu {
+    selected: usize,
+    choice: Rc<Cell<Option<PauseMenuItem>>>,
+}
+
+impl PauseMenu {
+    pub fn new(choice: Rc<Cell<Option<PauseMenuItem>>>) -> Self {
+        Self {
+            selected: 0,
+            choice,
+        }
+    }
+
+    pub fn selected(&self) -> PauseMenuItem {
+        PauseMenuItem::ALL[self.selected]
+    }
+
+    fn choose(&mut self, item: PauseMenuItem) -> Transition {
+        self.choice.set(Some(item));
+        match item {
+            PauseMenuItem::Resume | PauseMenuItem::Restart => Transition::Pop,
+            PauseMenuItem::Quit => Transition::Quit,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Stage for PauseMenu {
+    fn tick(&mut self, input: &InputService, _frame_time: Duration) -> Transition {
+        let len = PauseMenuItem::ALL.len();
+        if input.is_pressed(Action::MenuUp) {
+            self.selected = (self.selected + len - 1) % len;
+        }
+        if input.is_pressed(Action::MenuDown) {
+            self.selected = (self.selected + 1) % len;
+        }
+
+        if input.is_pressed(Action::Pause) {
+            self.choose(PauseMenuItem::Resume)
+        } else if input.is_pressed(Action::Confirm) {
+            self.choose(self.selected())
+        } else {
+            Transition::None
+        }
+    }
+
+    fn draw(&mut self) {
+        set_default_camera();
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), BACKDROP);
+
+        let top = screen_height() / 2.0 - LINE_HEIGHT;
+        for (i, item) in PauseMenuItem::ALL.into_iter().enumerate() {
+            let label = if i == self.selected {
+                format!("> {} <", item.label())
+            } else {
+                item.label().to_string()
+            };
+            draw_centered_text(&label, top + i as f32 * LINE_HEIGHT, FONT_SIZE);
+        }
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::KeyCode;
+
+    use super::*;
+
+    fn press(menu: &mut PauseMenu, key: KeyCode) -> Transition {
+        let mut input = InputService::default();
+        input.update_with(|pressed| pressed == key);
+        menu.tick(&input, Duration::ZERO)
+    }
+
+    #[test]
+    fn should_wrap_selection() {
+        let mut menu = PauseMenu::new(Default::default());
+        press(&mut menu, KeyCode::Up);
+        assert_eq!(menu.selected(), PauseMenuItem::Quit);
+        press(&mut menu, KeyCode::Down);
+        assert_eq!(menu.selected(), PauseMenuItem::Resume);
+    }
+
+    #[test]
+    fn should_report_restart_and_pop() {
+        let choice = Rc::default();
+        let mut menu = PauseMenu::new(Rc::clone(&choice));
+        press(&mut menu, KeyCode::Down);
+
+        let transition = press(&mut menu, KeyCode::Enter);
+
+        assert!(matches!(transition, Transition::Pop));
+        assert_eq!(choice.get(), Some(PauseMenuItem::Restart));
+    }
+
+    #[test]
+    fn should_resume_on_pause() {
+        let choice = Rc::default();
+        let mut menu = PauseMenu::new(Rc::clone(&choice));
+        press(&mut menu, KeyCode::Down);
+
+        let transition = press(&mut menu, KeyCode::Escape);
+
+        assert!(matches!(transition, Transition::Pop));
+        assert_eq!(choice.get(), Some(PauseMenuItem::Resume));
+    }
+}
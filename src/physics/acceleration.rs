@@ -2,6 +2,7 @@ use std::time::Duration;
 
 use super::velocity::Velocity;
 
+#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
 pub struct Acceleration {
     meters_on_second_on_second: f32,
 }
@@ -18,6 +19,8 @@ impl Acceleration {
     }
 }
 
+quantity!(Acceleration, meters_on_second_on_second);
+
 impl std::ops::Mul<Acceleration> for Duration {
     type Output = Velocity;
 
@@ -33,11 +36,3 @@ impl std::ops::Mul<Duration> for Acceleration {
         rhs * self
     }
 }
-
-impl std::ops::Mul<Acceleration> for f32 {
-    type Output = Acceleration;
-
-    fn mul(self, rhs: Acceleration) -> Self::Output {
-        Acceleration::from_meters_on_second_on_second(self * rhs.meters_on_second_on_second)
-    }
-}
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use super::acceleration::Acceleration;
+use super::length::Length;
+use super::velocity::Velocity;
+use super::xy::XY;
+
+/// How far a body starting at `velocity` travels during `duration` of constant `acceleration`.
+pub fn displacement(velocity: Velocity, acceleration: Acceleration, duration: Duration) -> Length {
+    velocity * duration + 0.5 * (acceleration * duration * duration)
+}
+
+/// A point moving under constant acceleration, advanced in closed form, so where it ends up
+/// doesn't depend on how the time is split into steps.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Body {
+    pub position: XY<Length>,
+    pub velocity: XY<Velocity>,
+}
+
+impl Body {
+    pub fn new(position: XY<Length>, velocity: XY<Velocity>) -> Self {
+        Self { position, velocity }
+    }
+
+    pub fn advance(&mut self, acceleration: XY<Acceleration>, duration: Duration) {
+        let velocity = self.velocity;
+        self.position += velocity.zip_with(acceleration, |velocity, acceleration| {
+            displacement(velocity, acceleration, duration)
+        });
+        self.velocity += acceleration * duration;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn body() -> impl Strategy<Value = Body> {
+        (
+            -10.0f32..10.0,
+            -10.0f32..10.0,
+            -10.0f32..10.0,
+            -10.0f32..10.0,
+        )
+            .prop_map(|(x, y, vx, vy)| {
+                Body::new(
+                    XY::new(Length::from_meters(x), Length::from_meters(y)),
+                    XY::new(
+                        Velocity::from_meters_on_second(vx),
+                        Velocity::from_meters_on_second(vy),
+                    ),
+                )
+            })
+    }
+
+    fn acceleration() -> impl Strategy<Value = XY<Acceleration>> {
+        (-30.0f32..30.0, -30.0f32..30.0).prop_map(|(x, y)| {
+            XY::new(
+                Acceleration::from_meters_on_second_on_second(x),
+                Acceleration::from_meters_on_second_on_second(y),
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn should_end_up_in_the_same_place_however_time_is_split(
+            body in body(),
+            acceleration in acceleration(),
+            steps in 1u32..200,
+        ) {
+            let total = Duration::from_secs(2);
+            let mut once = body;
+            once.advance(acceleration, total);
+            let mut stepped = body;
+            for _ in 0..steps {
+                stepped.advance(acceleration, total / steps);
+            }
+            let distance = (once.position - stepped.position).map(|axis| axis.abs());
+            prop_assert!(distance.x.max(distance.y) < Length::from_meters(1e-3));
+            let speed = (once.velocity - stepped.velocity).map(|axis| axis.abs());
+            prop_assert!(speed.x.max(speed.y) < Velocity::from_meters_on_second(1e-3));
+        }
+
+        #[test]
+        fn should_coast_without_acceleration(body in body(), millis in 0u64..5000) {
+            let duration = Duration::from_millis(millis);
+            let mut coasting = body;
+            coasting.advance(XY::default(), duration);
+            prop_assert_eq!(coasting.velocity, body.velocity);
+            prop_assert_eq!(coasting.position, body.position + body.velocity * duration);
+        }
+    }
+}
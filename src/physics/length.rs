@@ -1,3 +1,8 @@
+use std::time::Duration;
+
+use super::velocity::Velocity;
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
 pub struct Length {
     meters: f32,
 }
@@ -10,8 +15,14 @@ impl Length {
     pub const fn as_meters(&self) -> f32 {
         self.meters
     }
+}
+
+quantity!(Length, meters);
+
+impl std::ops::Div<Duration> for Length {
+    type Output = Velocity;
 
-    pub fn as_cm(&self) -> f32 {
-        100.0 * self.meters
+    fn div(self, rhs: Duration) -> Self::Output {
+        Velocity::from_meters_on_second(self.meters / rhs.as_secs_f32())
     }
 }
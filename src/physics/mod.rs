@@ -1,9 +1,21 @@
 pub use self::acceleration::*;
+pub use self::kinematics::*;
 pub use self::length::*;
+pub use self::scale::*;
 pub use self::velocity::*;
+pub use self::xy::*;
+
+// Declared first so the unit types below can use its macro
+#[macro_use]
+mod quantity;
 
 mod acceleration;
+mod kinematics;
 mod length;
+mod scale;
 mod velocity;
+mod xy;
 
 pub const EARTH_G: Acceleration = Acceleration::from_meters_on_second_on_second(9.8);
+/// The level's pixels are centimeters of the physics world.
+pub const WORLD_SCALE: Scale = Scale::from_pixels_per_meter(100.0);
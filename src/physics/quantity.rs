@@ -0,0 +1,172 @@
+/// Gives a unit type wrapping a single `f32` the arithmetic every physical quantity shares:
+/// adding and subtracting its own kind, scaling by plain numbers and taking ratios.
+macro_rules! quantity {
+    ($quantity:ident, $value:ident) => {
+        impl $quantity {
+            pub const ZERO: Self = Self { $value: 0.0 };
+
+            pub fn min(&self, other: Self) -> Self {
+                Self {
+                    $value: self.$value.min(other.$value),
+                }
+            }
+
+            pub fn max(&self, other: Self) -> Self {
+                Self {
+                    $value: self.$value.max(other.$value),
+                }
+            }
+
+            pub fn abs(&self) -> Self {
+                Self {
+                    $value: self.$value.abs(),
+                }
+            }
+
+            pub fn signum(&self) -> f32 {
+                self.$value.signum()
+            }
+        }
+
+        impl std::ops::Add for $quantity {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self {
+                    $value: self.$value + rhs.$value,
+                }
+            }
+        }
+
+        impl std::ops::AddAssign for $quantity {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl std::ops::Sub for $quantity {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self {
+                    $value: self.$value - rhs.$value,
+                }
+            }
+        }
+
+        impl std::ops::SubAssign for $quantity {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl std::ops::Neg for $quantity {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self {
+                    $value: -self.$value,
+                }
+            }
+        }
+
+        impl std::ops::Mul<f32> for $quantity {
+            type Output = Self;
+
+            fn mul(self, rhs: f32) -> Self::Output {
+                Self {
+                    $value: self.$value * rhs,
+                }
+            }
+        }
+
+        impl std::ops::Mul<$quantity> for f32 {
+            type Output = $quantity;
+
+            fn mul(self, rhs: $quantity) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl std::ops::Div<f32> for $quantity {
+            type Output = Self;
+
+            fn div(self, rhs: f32) -> Self::Output {
+                Self {
+                    $value: self.$value / rhs,
+                }
+            }
+        }
+
+        /// How many of `rhs` fit into `self`.
+        impl std::ops::Div for $quantity {
+            type Output = f32;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                self.$value / rhs.$value
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use proptest::prelude::*;
+
+    use crate::physics::*;
+
+    /// Values a level could plausibly produce, far from where `f32` loses precision.
+    fn value() -> impl Strategy<Value = f32> {
+        -1000.0f32..1000.0
+    }
+
+    fn duration() -> impl Strategy<Value = Duration> {
+        (1u64..10_000_000_000).prop_map(Duration::from_nanos)
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        let tolerance = 1e-4 * expected.abs().max(1.0);
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} differs from {expected}"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn should_add_and_subtract_like_numbers(a in value(), b in value(), scale in value()) {
+            let (x, y) = (Length::from_meters(a), Length::from_meters(b));
+            assert_close((x + y - y).as_meters(), a);
+            assert_close((x - y).as_meters(), -(y - x).as_meters());
+            assert_close((scale * (x + y)).as_meters(), (x * scale + y * scale).as_meters());
+            assert_eq!(x.min(y).max(x.max(y)), x.max(y));
+            assert_eq!(-(-x), x);
+
+            let mut v = Velocity::from_meters_on_second(a);
+            v += Velocity::from_meters_on_second(b);
+            v -= Velocity::from_meters_on_second(b);
+            assert_close(v.as_meters_on_second(), a);
+        }
+
+        #[test]
+        fn should_round_trip_through_time(a in value(), duration in duration()) {
+            let acceleration = Acceleration::from_meters_on_second_on_second(a);
+            let velocity = acceleration * duration;
+            assert_close((velocity / duration).as_meters_on_second_on_second(), a);
+            let length = velocity * duration;
+            assert_close((length / duration).as_meters_on_second(), velocity.as_meters_on_second());
+            assert_eq!(duration * velocity, velocity * duration);
+        }
+
+        #[test]
+        fn should_take_ratio_of_same_quantity(a in value(), b in value()) {
+            prop_assume!(b.abs() > 1e-3);
+            let ratio = Acceleration::from_meters_on_second_on_second(a)
+                / Acceleration::from_meters_on_second_on_second(b);
+            assert_close(ratio, a / b);
+            assert_close((ratio * Length::from_meters(b)).as_meters(), a);
+        }
+    }
+}
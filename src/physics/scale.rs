@@ -0,0 +1,54 @@
+use macroquad::prelude::{vec2, Vec2};
+
+use super::length::Length;
+use super::xy::XY;
+
+/// How many pixels of the level a meter of the game's physics takes up.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Scale {
+    pixels_per_meter: f32,
+}
+
+impl Scale {
+    pub const fn from_pixels_per_meter(pixels_per_meter: f32) -> Self {
+        Self { pixels_per_meter }
+    }
+
+    pub const fn pixels_per_meter(&self) -> f32 {
+        self.pixels_per_meter
+    }
+
+    pub fn to_pixels(&self, length: Length) -> f32 {
+        length.as_meters() * self.pixels_per_meter
+    }
+
+    pub fn to_length(&self, pixels: f32) -> Length {
+        Length::from_meters(pixels / self.pixels_per_meter)
+    }
+
+    pub fn xy_to_pixels(&self, xy: XY<Length>) -> Vec2 {
+        vec2(self.to_pixels(xy.x), self.to_pixels(xy.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::physics::WORLD_SCALE;
+
+    proptest! {
+        #[test]
+        fn should_convert_pixels_back_and_forth(pixels in -1e5f32..1e5, pixels_per_meter in 1.0f32..1000.0) {
+            let scale = Scale::from_pixels_per_meter(pixels_per_meter);
+            let round_trip = scale.to_pixels(scale.to_length(pixels));
+            prop_assert!((round_trip - pixels).abs() <= 1e-3 * pixels.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn should_keep_a_meter_a_hundred_pixels_in_the_world() {
+        assert_eq!(WORLD_SCALE.to_pixels(Length::from_meters(1.5)), 150.0);
+    }
+}
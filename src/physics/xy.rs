@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+/// A pair of quantities along the x and y axes, with the arithmetic of a `Vec2`.
+///
+/// It stands in for glam's `XY`, which as a foreign type can't be given operators for our units.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct XY<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> XY<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> XY<U> {
+        XY {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    /// Combines the matching axes of `self` and `other`.
+    pub fn zip_with<U, V>(self, other: XY<U>, mut f: impl FnMut(T, U) -> V) -> XY<V> {
+        XY {
+            x: f(self.x, other.x),
+            y: f(self.y, other.y),
+        }
+    }
+}
+
+impl<T: std::ops::Add<Output = T>> std::ops::Add for XY<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, T::add)
+    }
+}
+
+impl<T: std::ops::Add<Output = T> + Copy> std::ops::AddAssign for XY<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: std::ops::Sub<Output = T>> std::ops::Sub for XY<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, T::sub)
+    }
+}
+
+impl<T: std::ops::Sub<Output = T> + Copy> std::ops::SubAssign for XY<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: std::ops::Neg<Output = T>> std::ops::Neg for XY<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(T::neg)
+    }
+}
+
+impl<T: std::ops::Mul<f32, Output = T>> std::ops::Mul<f32> for XY<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        self.map(|value| value * rhs)
+    }
+}
+
+impl<T: std::ops::Div<f32, Output = T>> std::ops::Div<f32> for XY<T> {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        self.map(|value| value / rhs)
+    }
+}
+
+/// Integrates each axis over `rhs`, so velocities become lengths and accelerations velocities.
+impl<T: std::ops::Mul<Duration>> std::ops::Mul<Duration> for XY<T> {
+    type Output = XY<T::Output>;
+
+    fn mul(self, rhs: Duration) -> Self::Output {
+        self.map(|value| value * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::physics::{Acceleration, Velocity};
+
+    fn velocity() -> impl Strategy<Value = XY<Velocity>> {
+        (-100.0f32..100.0, -100.0f32..100.0).prop_map(|(x, y)| {
+            XY::new(
+                Velocity::from_meters_on_second(x),
+                Velocity::from_meters_on_second(y),
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn should_work_axis_by_axis(a in velocity(), b in velocity(), scale in -10.0f32..10.0) {
+            let sum = a + b;
+            prop_assert_eq!(sum.x, a.x + b.x);
+            prop_assert_eq!(sum.y, a.y + b.y);
+            prop_assert_eq!(a - b, a + -b);
+            prop_assert_eq!((a * scale).y, a.y * scale);
+
+            let mut moved = a;
+            moved += b;
+            moved -= b;
+            prop_assert!((moved.x - a.x).abs() <= Velocity::from_meters_on_second(1e-4));
+        }
+
+        #[test]
+        fn should_integrate_each_axis_over_time(a in velocity(), millis in 0u64..5000) {
+            let duration = Duration::from_millis(millis);
+            let length = a * duration;
+            prop_assert_eq!(length.x, a.x * duration);
+            prop_assert_eq!(length.y, a.y * duration);
+
+            let acceleration = a.map(|v| Acceleration::from_meters_on_second_on_second(v.as_meters_on_second()));
+            prop_assert_eq!((acceleration * duration).y, acceleration.y * duration);
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use macroquad::prelude::vec2;
+use nanoserde::{DeJson, SerJson};
+
+use crate::duck_params::DuckParams;
+use crate::input_service::InputSnapshot;
+use crate::save::Progress;
+
+const MAGIC: &[u8; 4] = b"DKRP";
+pub const REPLAY_VERSION: u32 = 4;
+/// Frames played per rendered frame while fast-forwarding.
+pub const FAST_FORWARD_SPEED: usize = 4;
+
+/// Input sampled during one frame together with that frame's duration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplayFrame {
+    /// In nanoseconds, so any value read from a file is a valid, exact duration.
+    frame_time: u64,
+    held: u8,
+    stick_x: f32,
+    stick_y: f32,
+}
+
+impl ReplayFrame {
+    pub fn new(snapshot: InputSnapshot, frame_time: Duration) -> Self {
+        let stick = snapshot.left_stick();
+        Self {
+            frame_time: frame_time.as_nanos() as u64,
+            held: snapshot.held_bits(),
+            stick_x: stick.x,
+            stick_y: stick.y,
+        }
+    }
+
+    pub fn snapshot(&self) -> InputSnapshot {
+        InputSnapshot::new(self.held, vec2(self.stick_x, self.stick_y))
+    }
+
+    pub fn frame_time(&self) -> Duration {
+        Duration::from_nanos(self.frame_time)
+    }
+
+    const SIZE: usize = 17;
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.frame_time.to_le_bytes());
+        bytes.push(self.held);
+        bytes.extend_from_slice(&self.stick_x.to_le_bytes());
+        bytes.extend_from_slice(&self.stick_y.to_le_bytes());
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        let f32_at = |at: usize| {
+            f32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+        };
+        let mut frame_time = [0; 8];
+        frame_time.copy_from_slice(&bytes[..8]);
+        Self {
+            frame_time: u64::from_le_bytes(frame_time),
+            held: bytes[8],
+            stick_x: f32_at(9),
+            stick_y: f32_at(13),
+        }
+    }
+}
+
+/// Binary replay: `DKRP` magic, then little-endian version, the header and fixed-size frames.
+/// The header holds the progress and duck params the recording started with, each as a
+/// little-endian `u32` length and then the text `save.json` and `duck.ron` use for them.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Replay {
+    progress: Progress,
+    duck_params: DuckParams,
+    frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    /// An empty recording that starts from `progress` with the duck tuned by `duck_params`.
+    pub fn new(progress: Progress, duck_params: DuckParams) -> Self {
+        Self {
+            progress,
+            duck_params,
+            frames: vec![],
+        }
+    }
+
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    pub fn duck_params(&self) -> DuckParams {
+        self.duck_params
+    }
+
+    pub fn record(&mut self, snapshot: InputSnapshot, frame_time: Duration) {
+        self.frames.push(ReplayFrame::new(snapshot, frame_time));
+    }
+
+    pub fn frames(&self) -> &[ReplayFrame] {
+        &self.frames
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&REPLAY_VERSION.to_le_bytes());
+        for text in [self.progress.serialize_json(), self.duck_params.to_ron()] {
+            bytes.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(text.as_bytes());
+        }
+        for frame in &self.frames {
+            frame.write(&mut bytes);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        if bytes.len() < 8 || &bytes[..4] != MAGIC {
+            return Err(ReplayError::NotAReplay);
+        }
+        let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        if version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+        let mut body = &bytes[8..];
+        let progress = Progress::deserialize_json(read_text(&mut body)?)
+            .map_err(|err| ReplayError::BadHeader(err.to_string()))?;
+        let duck_params = DuckParams::from_ron(read_text(&mut body)?)
+            .map_err(|err| ReplayError::BadHeader(err.to_string()))?;
+        if !body.len().is_multiple_of(ReplayFrame::SIZE) {
+            return Err(ReplayError::Truncated);
+        }
+        let frames = body
+            .chunks(ReplayFrame::SIZE)
+            .map(ReplayFrame::read)
+            .collect();
+        Ok(Self {
+            progress,
+            duck_params,
+            frames,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        std::fs::write(path, self.to_bytes()).map_err(ReplayError::Io)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        Self::from_bytes(&std::fs::read(path).map_err(ReplayError::Io)?)
+    }
+}
+
+/// Reads a length-prefixed header entry off the front of `bytes`.
+fn read_text<'a>(bytes: &mut &'a [u8]) -> Result<&'a str, ReplayError> {
+    let ends_early = || ReplayError::BadHeader("it ends early".into());
+    let (len, rest) = bytes.split_first_chunk::<4>().ok_or_else(ends_early)?;
+    let len = u32::from_le_bytes(*len) as usize;
+    if rest.len() < len {
+        return Err(ends_early());
+    }
+    let (text, rest) = rest.split_at(len);
+    *bytes = rest;
+    std::str::from_utf8(text).map_err(|err| ReplayError::BadHeader(err.to_string()))
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    NotAReplay,
+    UnsupportedVersion(u32),
+    BadHeader(String),
+    Truncated,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "can't access replay file: {}", err),
+            ReplayError::NotAReplay => write!(f, "file is not a replay"),
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "unsupported replay version {}", version)
+            }
+            ReplayError::BadHeader(cause) => write!(f, "can't read replay header: {}", cause),
+            ReplayError::Truncated => write!(f, "replay ends in the middle of a frame"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Feeds recorded frames back in order, with pause, single-step and fast-forward.
+pub struct ReplayPlayer {
+    replay: Replay,
+    cursor: usize,
+    paused: bool,
+    step_requested: bool,
+    fast_forward: bool,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            cursor: 0,
+            paused: false,
+            step_requested: false,
+            fast_forward: false,
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Plays exactly one frame on the next call to `next_frames` while paused.
+    pub fn step(&mut self) {
+        self.step_requested = true;
+    }
+
+    pub fn set_fast_forward(&mut self, fast_forward: bool) {
+        self.fast_forward = fast_forward;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.replay.frames.len()
+    }
+
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn len(&self) -> usize {
+        self.replay.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replay.frames.is_empty()
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Frames to tick during the current rendered frame.
+    pub fn next_frames(&mut self) -> &[ReplayFrame] {
+        let count = if self.paused {
+            std::mem::take(&mut self.step_requested) as usize
+        } else if self.fast_forward {
+            FAST_FORWARD_SPEED
+        } else {
+            1
+        };
+        let start = self.cursor;
+        self.cursor = (start + count).min(self.replay.frames.len());
+        &self.replay.frames[start..self.cursor]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_service::Action;
+
+    fn replay_of(len: usize) -> Replay {
+        let mut progress = Progress::default();
+        progress.completed_levels.push("level_1.json".into());
+        progress.best_times_ms.insert("level_1.json".into(), 12_345);
+        let duck_params = DuckParams {
+            jump_buffer_time: Duration::ZERO,
+            ..Default::default()
+        };
+        let mut replay = Replay::new(progress, duck_params);
+        for i in 0..len {
+            let mut snapshot = InputSnapshot::new(0, vec2(0.5, -0.25));
+            snapshot.set_held(Action::Jump, i % 2 == 0);
+            replay.record(snapshot, Duration::from_secs_f32(1.0 / 60.0));
+        }
+        replay
+    }
+
+    #[test]
+    fn should_round_trip_bytes() {
+        let replay = replay_of(3);
+        assert_eq!(Replay::from_bytes(&replay.to_bytes()).unwrap(), replay);
+        assert_eq!(
+            replay.frames()[0].frame_time(),
+            Duration::from_secs_f32(1.0 / 60.0)
+        );
+        assert!(replay.frames()[0].snapshot().is_held(Action::Jump));
+        assert_eq!(replay.frames()[1].snapshot().left_stick(), vec2(0.5, -0.25));
+    }
+
+    #[test]
+    fn should_keep_starting_progress_and_params() {
+        let replay = Replay::from_bytes(&replay_of(1).to_bytes()).unwrap();
+        assert_eq!(replay.progress().best_times_ms["level_1.json"], 12_345);
+        assert_eq!(replay.duck_params().jump_buffer_time, Duration::ZERO);
+    }
+
+    #[test]
+    fn should_reject_truncated_header() {
+        let bytes = replay_of(0).to_bytes();
+        assert!(matches!(
+            Replay::from_bytes(&bytes[..12]),
+            Err(ReplayError::BadHeader(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_other_versions() {
+        let mut bytes = replay_of(1).to_bytes();
+        bytes[4..8].copy_from_slice(&(REPLAY_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Replay::from_bytes(&bytes),
+            Err(ReplayError::UnsupportedVersion(version)) if version == REPLAY_VERSION + 1
+        ));
+        assert!(matches!(
+            Replay::from_bytes(b"not a replay"),
+            Err(ReplayError::NotAReplay)
+        ));
+    }
+
+    #[test]
+    fn should_reject_truncated_frames() {
+        let bytes = replay_of(2).to_bytes();
+        assert!(matches!(
+            Replay::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ReplayError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn should_step_only_when_requested_while_paused() {
+        let mut player = ReplayPlayer::new(replay_of(10));
+        player.toggle_pause();
+        assert!(player.next_frames().is_empty());
+
+        player.step();
+        assert_eq!(player.next_frames().len(), 1);
+        assert!(player.next_frames().is_empty());
+    }
+
+    #[test]
+    fn should_fast_forward_until_finished() {
+        let mut player = ReplayPlayer::new(replay_of(6));
+        player.set_fast_forward(true);
+        assert_eq!(player.next_frames().len(), FAST_FORWARD_SPEED);
+        assert_eq!(player.next_frames().len(), 6 - FAST_FORWARD_SPEED);
+        assert!(player.is_finished());
+        assert!(player.next_frames().is_empty());
+    }
+}
@@ -0,0 +1,23 @@
+//! Layouts of older save versions and how each one becomes the current `SaveData`.
+
+use nanoserde::DeJson;
+
+use super::{Progress, SaveData};
+
+/// Version 1 only remembered which levels were completed.
+#[derive(DeJson)]
+pub struct SaveFileV1 {
+    pub completed_levels: Vec<String>,
+}
+
+impl SaveFileV1 {
+    pub fn migrate(self) -> SaveData {
+        SaveData {
+            progress: Progress {
+                completed_levels: self.completed_levels,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
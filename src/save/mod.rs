@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use nanoserde::{DeJson, DeJsonErr, SerJson};
+
+use self::migration::*;
+
+mod migration;
+
+pub const SAVE_VERSION: u32 = 2;
+const DEFAULT_VOLUME: f32 = 1.0;
+const APP_DIR: &str = "duck_game";
+const SAVE_FILE: &str = "save.json";
+
+/// Everything that persists between runs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SaveData {
+    pub progress: Progress,
+    pub settings: Settings,
+}
+
+/// Campaign progress keyed by map path, so reordering the campaign keeps it.
+#[derive(DeJson, SerJson, Debug, Clone, Default, PartialEq)]
+pub struct Progress {
+    pub completed_levels: Vec<String>,
+    pub best_times_ms: HashMap<String, u64>,
+}
+
+#[derive(DeJson, SerJson, Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Overrides on top of `bindings.json`, in the same `{"Action": ["Key"]}` form.
+    pub bindings: HashMap<String, Vec<String>>,
+    /// Master volume in `[0, 1]`; saves written without it get the default.
+    #[nserde(default_with = "default_volume")]
+    pub volume: f32,
+    pub window_mode: WindowMode,
+}
+
+fn default_volume() -> f32 {
+    DEFAULT_VOLUME
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::new(),
+            volume: DEFAULT_VOLUME,
+            window_mode: WindowMode::Windowed,
+        }
+    }
+}
+
+#[derive(DeJson, SerJson, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMode {
+    Windowed,
+    Fullscreen,
+}
+
+/// On-disk layout of the current version.
+#[derive(DeJson, SerJson)]
+struct SaveFile {
+    version: u32,
+    progress: Progress,
+    settings: Settings,
+}
+
+#[derive(DeJson)]
+struct VersionProbe {
+    version: u32,
+}
+
+impl SaveData {
+    /// Reads any known save version, migrating older ones to the current layout.
+    pub fn from_json(json: &str) -> Result<Self, SaveError> {
+        let version = VersionProbe::deserialize_json(json)
+            .map_err(SaveError::Parse)?
+            .version;
+        let mut save_data = match version {
+            1 => SaveFileV1::deserialize_json(json)
+                .map_err(SaveError::Parse)?
+                .migrate(),
+            SAVE_VERSION => {
+                let file = SaveFile::deserialize_json(json).map_err(SaveError::Parse)?;
+                SaveData {
+                    progress: file.progress,
+                    settings: file.settings,
+                }
+            }
+            _ => return Err(SaveError::UnsupportedVersion(version)),
+        };
+        let volume = save_data.settings.volume;
+        if !volume.is_finite() {
+            return Err(SaveError::InvalidVolume(volume));
+        }
+        save_data.settings.volume = volume.clamp(0.0, 1.0);
+        Ok(save_data)
+    }
+
+    pub fn to_json(&self) -> String {
+        SaveFile {
+            version: SAVE_VERSION,
+            progress: self.progress.clone(),
+            settings: self.settings.clone(),
+        }
+        .serialize_json()
+    }
+}
+
+/// Reads and writes the save file; writes go through a temporary file and a rename.
+pub struct SaveStore {
+    path: PathBuf,
+}
+
+impl SaveStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The save file in the user's config directory, if the platform has one.
+    pub fn in_config_dir() -> Option<Self> {
+        dirs::config_dir().map(|dir| Self::new(dir.join(APP_DIR).join(SAVE_FILE)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A missing file is a fresh start, not an error.
+    pub fn load(&self) -> Result<SaveData, SaveError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(json) => SaveData::from_json(&json),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(SaveData::default()),
+            Err(err) => Err(SaveError::Io(err)),
+        }
+    }
+
+    pub fn save(&self, save_data: &SaveData) -> Result<(), SaveError> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(SaveError::Io)?;
+        }
+        // The rename replaces the old save in one step, so a crash leaves either version intact
+        let tmp_path = self.path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&tmp_path).map_err(SaveError::Io)?;
+        file.write_all(save_data.to_json().as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(SaveError::Io)?;
+        std::fs::rename(&tmp_path, &self.path).map_err(SaveError::Io)
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Parse(DeJsonErr),
+    UnsupportedVersion(u32),
+    InvalidVolume(f32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "can't access save file: {}", err),
+            SaveError::Parse(err) => write!(f, "can't parse save file: {}", err),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "unsupported save version {}", version)
+            }
+            SaveError::InvalidVolume(volume) => {
+                write!(f, "volume must be between 0 and 1, got {}", volume)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn save_data() -> SaveData {
+        let mut save_data = SaveData::default();
+        save_data.progress.completed_levels.push("map.json".into());
+        save_data
+            .progress
+            .best_times_ms
+            .insert("map.json".into(), 12_345);
+        save_data
+            .settings
+            .bindings
+            .insert("Jump".into(), vec!["Up".into()]);
+        save_data.settings.window_mode = WindowMode::Fullscreen;
+        save_data
+    }
+
+    fn temp_store(name: &str) -> SaveStore {
+        let dir = std::env::temp_dir().join(format!("duck_save_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        SaveStore::new(dir.join(SAVE_FILE))
+    }
+
+    #[test]
+    fn should_round_trip_json() {
+        let save_data = save_data();
+        assert_eq!(
+            SaveData::from_json(&save_data.to_json()).unwrap(),
+            save_data
+        );
+    }
+
+    #[test]
+    fn should_migrate_version_1() {
+        let json = r#"{"version": 1, "completed_levels": ["map.json"]}"#;
+        let save_data = SaveData::from_json(json).unwrap();
+        assert_eq!(save_data.progress.completed_levels, ["map.json"]);
+        assert_eq!(save_data.settings, Settings::default());
+    }
+
+    #[test]
+    fn should_default_volume_missing_from_save() {
+        let json = r#"{"version": 2, "progress": {"completed_levels": [], "best_times_ms": {}},
+                       "settings": {"bindings": {}, "window_mode": "Windowed"}}"#;
+        assert_eq!(
+            SaveData::from_json(json).unwrap().settings.volume,
+            DEFAULT_VOLUME
+        );
+    }
+
+    #[test]
+    fn should_reject_non_finite_volume() {
+        let mut save_data = save_data();
+        save_data.settings.volume = 0.5;
+        // Too big for an `f32`, so it reads as infinity
+        let json = save_data.to_json().replace("0.5", "1e39");
+        assert!(matches!(
+            SaveData::from_json(&json),
+            Err(SaveError::InvalidVolume(volume)) if volume.is_infinite()
+        ));
+    }
+
+    #[test]
+    fn should_reject_newer_versions() {
+        let json = format!(r#"{{"version": {}}}"#, SAVE_VERSION + 1);
+        assert!(matches!(
+            SaveData::from_json(&json),
+            Err(SaveError::UnsupportedVersion(version)) if version == SAVE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn should_start_fresh_without_save_file() {
+        let store = temp_store("fresh");
+        assert_eq!(store.load().unwrap(), SaveData::default());
+    }
+
+    #[test]
+    fn should_replace_save_without_leaving_temp_file() {
+        let store = temp_store("replace");
+        store.save(&SaveData::default()).unwrap();
+        store.save(&save_data()).unwrap();
+
+        assert_eq!(store.load().unwrap(), save_data());
+        let dir = store.path().parent().unwrap();
+        assert_eq!(std::fs::read_dir(dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,759 @@
+use std::time::Duration;
+
+use macroquad::prelude::Vec2;
+use macroquad_platformer::World;
+
+use crate::duck::{Duck, DuckAbilities, DuckInput};
+use crate::duck_params::DuckParams;
+use crate::fixed_timestep::PHYSICS_TIMESTEP;
+use crate::moving_platforms::{MovingPlatforms, PlatformSpec};
+use crate::slopes::Slopes;
+use crate::surfaces::Surfaces;
+use crate::water::Water;
+
+pub const DEFAULT_TIMESTEP: Duration = PHYSICS_TIMESTEP;
+
+/// Steps a duck against a platformer world without a window or textures.
+pub struct Simulation {
+    world: World,
+    duck: Duck,
+    platforms: MovingPlatforms,
+    timestep: Duration,
+    elapsed: Duration,
+}
+
+impl Simulation {
+    pub fn new(mut world: World, duck_pos: Vec2, duck_size: Vec2, timestep: Duration) -> Self {
+        let duck = Duck::headless(&mut world, duck_pos, duck_size);
+        Self {
+            world,
+            duck,
+            platforms: MovingPlatforms::default(),
+            timestep,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn with_params(mut self, params: DuckParams) -> Self {
+        self.duck.set_params(params);
+        self
+    }
+
+    pub fn with_abilities(mut self, abilities: DuckAbilities) -> Self {
+        self.duck.set_abilities(abilities);
+        self
+    }
+
+    pub fn with_water(mut self, water: Water) -> Self {
+        self.duck.set_water(water);
+        self
+    }
+
+    pub fn with_surfaces(mut self, surfaces: Surfaces) -> Self {
+        self.duck.set_surfaces(surfaces);
+        self
+    }
+
+    pub fn with_slopes(mut self, slopes: Slopes) -> Self {
+        self.duck.set_slopes(slopes);
+        self
+    }
+
+    pub fn with_platforms(mut self, specs: Vec<PlatformSpec>) -> Self {
+        self.platforms = MovingPlatforms::new(&mut self.world, specs);
+        self
+    }
+
+    pub fn step(&mut self, input: DuckInput) {
+        self.duck.step(&mut self.world, input, self.timestep);
+        self.platforms
+            .step(&mut self.world, &self.duck, self.timestep);
+        self.elapsed += self.timestep;
+    }
+
+    /// Repeats `input` for as many whole timesteps as fit into `duration`.
+    pub fn run(&mut self, input: DuckInput, duration: Duration) {
+        let steps = duration.as_nanos() / self.timestep.as_nanos();
+        for _ in 0..steps {
+            self.step(input);
+        }
+    }
+
+    pub fn platforms(&self) -> &MovingPlatforms {
+        &self.platforms
+    }
+
+    pub fn duck(&self) -> &Duck {
+        &self.duck
+    }
+
+    pub fn duck_mut(&mut self) -> &mut Duck {
+        &mut self.duck
+    }
+
+    pub fn duck_pos(&self) -> Vec2 {
+        self.duck.pos(&self.world)
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    pub fn timestep(&self) -> Duration {
+        self.timestep
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::{vec2, Rect};
+    use macroquad_platformer::Tile;
+
+    use super::*;
+    use crate::duck::*;
+    use crate::duck_animation::DuckPose;
+    use crate::physics::{Velocity, WORLD_SCALE};
+    use crate::slopes::Slope;
+    use crate::surfaces::Surface;
+    use crate::test_worlds::*;
+
+    const DUCK_WIDTH: f32 = 50.0;
+    const DUCK_HEIGHT: f32 = 72.0;
+
+    fn floor_world() -> World {
+        let mut colliders = vec![Tile::Empty; WIDTH * HEIGHT];
+        colliders[(HEIGHT - 1) * WIDTH..].fill(Tile::Solid);
+        world_from(colliders)
+    }
+
+    /// Floor under the left half only, so walking right runs off a ledge.
+    fn ledge_world() -> World {
+        let mut colliders = vec![Tile::Empty; WIDTH * HEIGHT];
+        colliders[(HEIGHT - 1) * WIDTH..][..WIDTH / 2].fill(Tile::Solid);
+        world_from(colliders)
+    }
+
+    const CEILING_ROW: usize = 4;
+    const CEILING_Y: f32 = (CEILING_ROW + 1) as f32 * TILE_SIZE;
+
+    /// Floor with a ceiling across the map a few rows below the top.
+    fn ceiling_world() -> World {
+        let mut colliders = vec![Tile::Empty; WIDTH * HEIGHT];
+        colliders[(HEIGHT - 1) * WIDTH..].fill(Tile::Solid);
+        colliders[CEILING_ROW * WIDTH..][..WIDTH].fill(Tile::Solid);
+        world_from(colliders)
+    }
+
+    const WALL_COLUMN: usize = 20;
+    const WALL_X: f32 = WALL_COLUMN as f32 * TILE_SIZE;
+
+    /// Floor with a wall as tall as the map standing on it.
+    fn wall_world() -> World {
+        let mut colliders = vec![Tile::Empty; WIDTH * HEIGHT];
+        colliders[(HEIGHT - 1) * WIDTH..].fill(Tile::Solid);
+        for row in colliders.chunks_mut(WIDTH) {
+            row[WALL_COLUMN] = Tile::Solid;
+        }
+        world_from(colliders)
+    }
+
+    /// A duck high up against the left side of the wall, falling and pushing into it.
+    fn simulation_on_wall(abilities: DuckAbilities) -> Simulation {
+        let mut simulation = Simulation::new(
+            wall_world(),
+            vec2(WALL_X - DUCK_WIDTH, TILE_SIZE),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            DEFAULT_TIMESTEP,
+        )
+        .with_abilities(abilities);
+        simulation.run(push_right(), Duration::from_millis(500));
+        simulation
+    }
+
+    fn push_right() -> DuckInput {
+        DuckInput {
+            horizontal: 1.0,
+            ..Default::default()
+        }
+    }
+
+    /// Walks right until the first step without ground under the duck.
+    fn simulation_off_ledge() -> Simulation {
+        let mut simulation = Simulation::new(
+            ledge_world(),
+            vec2(100.0, FLOOR_Y - DUCK_HEIGHT),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            DEFAULT_TIMESTEP,
+        );
+        let walk = DuckInput {
+            horizontal: 1.0,
+            ..Default::default()
+        };
+        while simulation.duck().velocity().y == Velocity::ZERO {
+            simulation.step(walk);
+        }
+        simulation
+    }
+
+    fn press_jump() -> DuckInput {
+        DuckInput {
+            jump_pressed: true,
+            ..jump()
+        }
+    }
+
+    fn simulation_at_rest(timestep: Duration) -> Simulation {
+        let mut simulation = Simulation::new(
+            floor_world(),
+            vec2(100.0, FLOOR_Y - DUCK_HEIGHT - 10.0),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            timestep,
+        );
+        simulation.run(DuckInput::default(), Duration::from_secs(1));
+        simulation
+    }
+
+    fn jump() -> DuckInput {
+        DuckInput {
+            jump: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_land_on_floor() {
+        let simulation = simulation_at_rest(DEFAULT_TIMESTEP);
+        assert_eq!(simulation.duck_pos().y, FLOOR_Y - DUCK_HEIGHT);
+        assert!(simulation.duck().velocity().y == Velocity::ZERO);
+    }
+
+    #[test]
+    fn should_reach_max_jump_height_holding_space() {
+        // Explicit Euler overshoots the apex by about v0 * dt / 2, so use a fine step
+        let mut simulation = simulation_at_rest(Duration::from_millis(1));
+        let ground_y = simulation.duck_pos().y;
+
+        simulation.step(DuckInput {
+            jump_pressed: true,
+            ..jump()
+        });
+        let mut peak_y = simulation.duck_pos().y;
+        for _ in 0..1000 {
+            simulation.step(jump());
+            peak_y = peak_y.min(simulation.duck_pos().y);
+        }
+
+        let jump_height = ground_y - peak_y;
+        let max_jump_height = WORLD_SCALE.to_pixels(DuckParams::default().max_jump_height);
+        assert!(
+            (jump_height - max_jump_height).abs() <= 2.0,
+            "jump height {jump_height} differs from {max_jump_height}"
+        );
+    }
+
+    #[test]
+    fn should_jump_during_coyote_time() {
+        let mut simulation = simulation_off_ledge();
+        simulation.run(DuckInput::default(), DuckParams::default().coyote_time / 2);
+        simulation.step(press_jump());
+        assert!(simulation.duck().velocity().y < Velocity::ZERO);
+    }
+
+    #[test]
+    fn should_not_jump_after_coyote_time() {
+        let mut simulation = simulation_off_ledge();
+        simulation.run(DuckInput::default(), DuckParams::default().coyote_time);
+        simulation.step(press_jump());
+        assert!(simulation.duck().velocity().y > Velocity::ZERO);
+    }
+
+    /// Drops the duck onto the floor, tapping jump `before_landing` ahead of touchdown.
+    /// The tap releases right away, since holding jump would hover and delay the landing.
+    fn landing_with_jump_pressed(before_landing: Duration) -> Simulation {
+        let drop = || {
+            Simulation::new(
+                floor_world(),
+                vec2(100.0, FLOOR_Y - DUCK_HEIGHT - 200.0),
+                vec2(DUCK_WIDTH, DUCK_HEIGHT),
+                DEFAULT_TIMESTEP,
+            )
+        };
+        let mut probe = drop();
+        while probe.duck_pos().y < FLOOR_Y - DUCK_HEIGHT {
+            probe.step(DuckInput::default());
+        }
+        let press_at = probe.elapsed() - before_landing;
+
+        let mut simulation = drop();
+        simulation.run(DuckInput::default(), press_at);
+        simulation.step(DuckInput {
+            jump_pressed: true,
+            ..Default::default()
+        });
+        simulation.run(DuckInput::default(), before_landing + 2 * DEFAULT_TIMESTEP);
+        simulation
+    }
+
+    #[test]
+    fn should_buffer_jump_pressed_before_landing() {
+        let simulation = landing_with_jump_pressed(DuckParams::default().jump_buffer_time / 2);
+        assert!(simulation.duck().velocity().y < Velocity::ZERO);
+    }
+
+    #[test]
+    fn should_jump_on_press_without_jump_buffer() {
+        let mut simulation = simulation_at_rest(DEFAULT_TIMESTEP).with_params(DuckParams {
+            jump_buffer_time: Duration::ZERO,
+            ..Default::default()
+        });
+        simulation.step(press_jump());
+        assert!(simulation.duck().velocity().y < Velocity::ZERO);
+    }
+
+    #[test]
+    fn should_forget_jump_pressed_long_before_landing() {
+        let simulation = landing_with_jump_pressed(2 * DuckParams::default().jump_buffer_time);
+        assert!(simulation.duck().velocity().y == Velocity::ZERO);
+    }
+
+    #[test]
+    fn should_reach_max_move_velocity() {
+        let mut simulation = simulation_at_rest(DEFAULT_TIMESTEP);
+        let input = DuckInput {
+            horizontal: 1.0,
+            ..Default::default()
+        };
+        simulation.run(input, Duration::from_secs(1));
+        assert!(simulation.duck().velocity().x == DuckParams::default().max_move_velocity);
+    }
+
+    #[test]
+    fn should_use_tuned_move_velocity() {
+        let params = DuckParams {
+            max_move_velocity: Velocity::from_meters_on_second(1.0),
+            ..Default::default()
+        };
+        let mut simulation = simulation_at_rest(DEFAULT_TIMESTEP).with_params(params);
+        let input = DuckInput {
+            horizontal: 1.0,
+            ..Default::default()
+        };
+        simulation.run(input, Duration::from_secs(1));
+        assert!(simulation.duck().velocity().x == params.max_move_velocity);
+    }
+
+    #[test]
+    fn should_scale_acceleration_by_analog_input() {
+        let mut full = simulation_at_rest(DEFAULT_TIMESTEP);
+        let mut half = simulation_at_rest(DEFAULT_TIMESTEP);
+        for _ in 0..10 {
+            full.step(DuckInput {
+                horizontal: 1.0,
+                ..Default::default()
+            });
+            half.step(DuckInput {
+                horizontal: 0.5,
+                ..Default::default()
+            });
+        }
+        let full_velocity = full.duck().velocity().x.as_meters_on_second();
+        let half_velocity = half.duck().velocity().x.as_meters_on_second();
+        assert!((full_velocity - 2.0 * half_velocity).abs() < 1e-4);
+    }
+
+    #[test]
+    fn should_be_deterministic() {
+        let inputs = [
+            DuckInput {
+                horizontal: 1.0,
+                jump: true,
+                jump_pressed: true,
+                ..Default::default()
+            },
+            DuckInput {
+                horizontal: 1.0,
+                jump: true,
+                ..Default::default()
+            },
+            DuckInput::default(),
+        ];
+        let run = || {
+            let mut simulation = simulation_at_rest(DEFAULT_TIMESTEP);
+            for input in inputs.iter().cycle().take(300) {
+                simulation.step(*input);
+            }
+            simulation.duck_pos()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn should_knock_duck_back_and_shrug_off_hits_while_invulnerable() {
+        let mut simulation = simulation_at_rest(DEFAULT_TIMESTEP);
+        let start = simulation.duck_pos();
+
+        assert!(simulation.duck_mut().hurt(-1.0));
+        assert_eq!(simulation.duck().health(), MAX_HEALTH - 1);
+        simulation.run(DuckInput::default(), Duration::from_millis(100));
+        assert!(simulation.duck_pos().x < start.x);
+        assert!(simulation.duck_pos().y < start.y);
+
+        assert!(!simulation.duck_mut().hurt(-1.0));
+        simulation.run(
+            DuckInput::default(),
+            INVULNERABILITY_TIME + DEFAULT_TIMESTEP,
+        );
+        assert!(!simulation.duck().is_invulnerable());
+        assert!(simulation.duck_mut().hurt(1.0));
+        assert_eq!(simulation.duck().health(), MAX_HEALTH - 2);
+    }
+
+    #[test]
+    fn should_run_out_of_health() {
+        let mut simulation = simulation_at_rest(DEFAULT_TIMESTEP);
+        for _ in 0..MAX_HEALTH {
+            assert!(simulation.duck().is_alive());
+            assert!(simulation.duck_mut().hurt(1.0));
+            simulation.run(
+                DuckInput::default(),
+                INVULNERABILITY_TIME + DEFAULT_TIMESTEP,
+            );
+        }
+        assert!(!simulation.duck().is_alive());
+
+        simulation.duck_mut().restore_health();
+        assert_eq!(simulation.duck().health(), MAX_HEALTH);
+    }
+
+    #[test]
+    fn should_slide_down_wall_only_when_enabled() {
+        let sliding = simulation_on_wall(DuckAbilities {
+            wall_slide: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            sliding.duck().velocity().y,
+            DuckParams::default().wall_slide_velocity
+        );
+        assert_eq!(sliding.duck_pos().x, WALL_X - DUCK_WIDTH);
+
+        let falling = simulation_on_wall(DuckAbilities::default());
+        assert!(falling.duck().velocity().y > 2.0 * DuckParams::default().wall_slide_velocity);
+    }
+
+    #[test]
+    fn should_wall_jump_away_from_wall_only_when_enabled() {
+        let abilities = DuckAbilities {
+            wall_slide: true,
+            wall_jump: true,
+        };
+        let mut simulation = simulation_on_wall(abilities);
+        simulation.step(press_jump());
+        let velocity = simulation.duck().velocity();
+        assert_eq!(velocity.x, -DuckParams::default().wall_jump_push);
+        assert!(velocity.y < Velocity::ZERO);
+        simulation.run(jump(), Duration::from_millis(200));
+        assert!(simulation.duck_pos().x < WALL_X - DUCK_WIDTH - 20.0);
+
+        let mut simulation = simulation_on_wall(DuckAbilities {
+            wall_jump: false,
+            ..abilities
+        });
+        simulation.step(press_jump());
+        assert!(simulation.duck().velocity().y > Velocity::ZERO);
+    }
+
+    /// Water 400px deep over the whole floor.
+    fn pool() -> Water {
+        Water::new(vec![Rect::new(
+            0.0,
+            FLOOR_Y - 400.0,
+            WIDTH as f32 * TILE_SIZE,
+            400.0,
+        )])
+    }
+
+    fn simulation_in_pool(duck_y: f32) -> Simulation {
+        Simulation::new(
+            floor_world(),
+            vec2(100.0, duck_y),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            DEFAULT_TIMESTEP,
+        )
+        .with_water(pool())
+    }
+
+    fn submersion(simulation: &Simulation) -> f32 {
+        let pos = simulation.duck_pos();
+        pool().submersion(Rect::new(pos.x, pos.y, DUCK_WIDTH, DUCK_HEIGHT))
+    }
+
+    #[test]
+    fn should_float_at_waterline() {
+        let mut simulation = simulation_in_pool(FLOOR_Y - 600.0);
+        simulation.run(DuckInput::default(), Duration::from_secs(5));
+
+        assert!((submersion(&simulation) - DuckParams::default().float_submersion).abs() < 0.05);
+        assert!(simulation.duck().velocity().y.abs() < Velocity::from_meters_on_second(0.2));
+    }
+
+    #[test]
+    fn should_paddle_up_from_under_water_and_leap_out_at_surface() {
+        let mut simulation = simulation_in_pool(FLOOR_Y - DUCK_HEIGHT - 10.0);
+        simulation.step(press_jump());
+        assert!(simulation.duck().velocity().y < -0.9 * DuckParams::default().paddle_velocity);
+        assert_eq!(submersion(&simulation), 1.0);
+
+        simulation.run(DuckInput::default(), Duration::from_secs(5));
+        simulation.step(press_jump());
+        simulation.run(jump(), Duration::from_millis(150));
+        assert_eq!(submersion(&simulation), 0.0);
+    }
+
+    /// Floor tiles all made of `surface`.
+    fn simulation_on_surface(surface: Surface) -> Simulation {
+        let mut cells = vec![Surface::Ground; WIDTH * HEIGHT];
+        cells[(HEIGHT - 1) * WIDTH..].fill(surface);
+        Simulation::new(
+            floor_world(),
+            vec2(100.0, FLOOR_Y - DUCK_HEIGHT - 10.0),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            DEFAULT_TIMESTEP,
+        )
+        .with_surfaces(Surfaces::new(cells, grid()))
+    }
+
+    fn velocity_after_letting_go(surface: Surface) -> Velocity {
+        let mut simulation = simulation_on_surface(surface);
+        simulation.run(push_right(), Duration::from_secs(1));
+        simulation.run(DuckInput::default(), Duration::from_millis(500));
+        simulation.duck().velocity().x
+    }
+
+    #[test]
+    fn should_slide_on_ice_and_slow_down_in_mud() {
+        assert!(
+            velocity_after_letting_go(Surface::Ice)
+                > 2.0 * velocity_after_letting_go(Surface::Ground)
+        );
+
+        let mut simulation = simulation_on_surface(Surface::Mud);
+        simulation.run(push_right(), Duration::from_secs(1));
+        assert_eq!(
+            simulation.duck().velocity().x,
+            Surface::Mud.speed() * DuckParams::default().max_move_velocity
+        );
+    }
+
+    #[test]
+    fn should_launch_off_spring_higher_than_a_jump() {
+        let mut simulation = simulation_on_surface(Surface::Spring);
+        let mut peak_y = simulation.duck_pos().y;
+        for _ in 0..240 {
+            simulation.step(DuckInput::default());
+            peak_y = peak_y.min(simulation.duck_pos().y);
+        }
+        let rise = FLOOR_Y - DUCK_HEIGHT - peak_y;
+        assert!(rise > 1.5 * WORLD_SCALE.to_pixels(DuckParams::default().max_jump_height));
+    }
+
+    const RAMP_COLUMN: usize = 10;
+    const PLATEAU_Y: f32 = FLOOR_Y - TILE_SIZE;
+
+    /// A ramp of `ramp` tiles from the floor up to a plateau one tile high that runs to the
+    /// right edge of the map.
+    fn simulation_by_ramp(ramp: &[Slope]) -> Simulation {
+        let mut colliders = vec![Tile::Empty; WIDTH * HEIGHT];
+        colliders[(HEIGHT - 1) * WIDTH..].fill(Tile::Solid);
+        colliders[(HEIGHT - 2) * WIDTH..][RAMP_COLUMN + ramp.len()..WIDTH].fill(Tile::Solid);
+        let world = world_from(colliders);
+        let mut cells = vec![None; WIDTH * HEIGHT];
+        for (column, slope) in ramp.iter().enumerate() {
+            cells[(HEIGHT - 2) * WIDTH + RAMP_COLUMN + column] = Some(*slope);
+        }
+        let mut simulation = Simulation::new(
+            world,
+            vec2(100.0, FLOOR_Y - DUCK_HEIGHT),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            DEFAULT_TIMESTEP,
+        )
+        .with_slopes(Slopes::new(cells, grid()));
+        simulation.step(DuckInput::default());
+        simulation
+    }
+
+    /// Walks for `duration`, failing if the duck ever leaves the ground on the way.
+    fn walk_without_leaving_ground(
+        simulation: &mut Simulation,
+        horizontal: f32,
+        duration: Duration,
+    ) {
+        let input = DuckInput {
+            horizontal,
+            ..Default::default()
+        };
+        for _ in 0..duration.as_nanos() / simulation.timestep().as_nanos() {
+            simulation.step(input);
+            let pose = simulation.duck().animation().pose();
+            assert!(
+                matches!(pose, DuckPose::Idle | DuckPose::Walk),
+                "duck at {} is {:?}",
+                simulation.duck_pos(),
+                pose
+            );
+        }
+    }
+
+    #[test]
+    fn should_walk_up_and_down_ramps_without_leaving_ground() {
+        for ramp in [&[Slope::RISE][..], &[Slope::RISE_LOW, Slope::RISE_HIGH][..]] {
+            let mut simulation = simulation_by_ramp(ramp);
+            walk_without_leaving_ground(&mut simulation, 1.0, Duration::from_secs(2));
+            assert_eq!(simulation.duck_pos().y + DUCK_HEIGHT, PLATEAU_Y);
+
+            walk_without_leaving_ground(&mut simulation, -1.0, Duration::from_secs(2));
+            assert_eq!(simulation.duck_pos().y + DUCK_HEIGHT, FLOOR_Y);
+        }
+    }
+
+    #[test]
+    fn should_land_on_slope_and_jump_off_it() {
+        let mut simulation = simulation_by_ramp(&[Slope::RISE]);
+        let ramp_x = RAMP_COLUMN as f32 * TILE_SIZE;
+        simulation
+            .world
+            .set_actor_position(simulation.duck.actor(), vec2(ramp_x - 40.0, 300.0));
+        simulation.run(DuckInput::default(), Duration::from_secs(1));
+        let rest_y = simulation.duck_pos().y;
+        assert!(rest_y + DUCK_HEIGHT < FLOOR_Y && rest_y + DUCK_HEIGHT > PLATEAU_Y);
+        assert_eq!(simulation.duck().velocity().y, Velocity::ZERO);
+
+        simulation.step(press_jump());
+        simulation.step(jump());
+        assert!(simulation.duck_pos().y < rest_y);
+    }
+
+    fn platform(path: Vec<Vec2>, jump_through: bool) -> PlatformSpec {
+        PlatformSpec {
+            path,
+            size: vec2(96.0, 32.0),
+            speed: Velocity::from_meters_on_second(1.0),
+            jump_through,
+        }
+    }
+
+    /// A duck standing on the left of a platform that starts at the first point of `path`.
+    fn simulation_on_platform(path: Vec<Vec2>, jump_through: bool) -> Simulation {
+        let start = path[0];
+        Simulation::new(
+            floor_world(),
+            vec2(start.x + 20.0, start.y - DUCK_HEIGHT),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            DEFAULT_TIMESTEP,
+        )
+        .with_platforms(vec![platform(path, jump_through)])
+    }
+
+    fn platform_rect(simulation: &Simulation) -> Rect {
+        simulation.platforms().platforms()[0].rect()
+    }
+
+    fn duck_bottom(simulation: &Simulation) -> f32 {
+        simulation.duck_pos().y + DUCK_HEIGHT
+    }
+
+    /// Landing on a solid leaves the duck resting 1px above it.
+    fn is_on_platform(simulation: &Simulation) -> bool {
+        let gap = platform_rect(simulation).top() - duck_bottom(simulation);
+        (0.0..=1.0).contains(&gap)
+    }
+
+    #[test]
+    fn should_carry_duck_along_moving_platform() {
+        let path = vec![vec2(200.0, 320.0), vec2(600.0, 320.0)];
+        let mut simulation = simulation_on_platform(path, false);
+
+        simulation.run(DuckInput::default(), Duration::from_secs(1));
+
+        let platform = platform_rect(&simulation);
+        assert!(platform.x > 290.0);
+        assert_eq!(simulation.duck_pos().x - platform.x, 20.0);
+        assert!(is_on_platform(&simulation));
+    }
+
+    #[test]
+    fn should_ride_platform_up_and_down() {
+        let path = vec![vec2(200.0, 320.0), vec2(200.0, 120.0)];
+        let mut simulation = simulation_on_platform(path, false);
+
+        for _ in 0..4 {
+            simulation.run(DuckInput::default(), Duration::from_millis(700));
+            assert!(is_on_platform(&simulation));
+        }
+    }
+
+    #[test]
+    fn should_stop_duck_at_ceiling_when_platform_rises_into_it() {
+        let path = vec![vec2(200.0, 320.0), vec2(200.0, CEILING_Y + 40.0)];
+        let mut simulation = Simulation::new(
+            ceiling_world(),
+            vec2(220.0, 320.0 - DUCK_HEIGHT),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            DEFAULT_TIMESTEP,
+        )
+        .with_platforms(vec![platform(path, false)]);
+
+        let mut highest = f32::MAX;
+        for _ in 0..240 {
+            simulation.step(DuckInput::default());
+            highest = highest.min(simulation.duck_pos().y);
+        }
+
+        assert_eq!(highest, CEILING_Y, "the duck's head stops at the ceiling");
+    }
+
+    #[test]
+    fn should_drop_through_jump_through_platform() {
+        let path = vec![vec2(200.0, 320.0), vec2(600.0, 320.0)];
+        let mut simulation = simulation_on_platform(path, true);
+        simulation.run(DuckInput::default(), Duration::from_millis(500));
+        assert!(is_on_platform(&simulation));
+
+        simulation.step(DuckInput {
+            down: true,
+            jump_pressed: true,
+            ..jump()
+        });
+        simulation.run(DuckInput::default(), Duration::from_secs(2));
+
+        assert_eq!(duck_bottom(&simulation), FLOOR_Y);
+    }
+
+    #[test]
+    fn should_jump_up_through_jump_through_platform() {
+        let top = FLOOR_Y - 120.0;
+        let mut simulation = Simulation::new(
+            floor_world(),
+            vec2(220.0, FLOOR_Y - DUCK_HEIGHT),
+            vec2(DUCK_WIDTH, DUCK_HEIGHT),
+            DEFAULT_TIMESTEP,
+        )
+        .with_platforms(vec![platform(
+            vec![vec2(200.0, top), vec2(210.0, top)],
+            true,
+        )]);
+
+        simulation.step(press_jump());
+        simulation.run(jump(), Duration::from_secs(2));
+
+        assert!(is_on_platform(&simulation));
+    }
+}
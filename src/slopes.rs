@@ -0,0 +1,141 @@
+use macroquad::prelude::Rect;
+
+use crate::assets_server::AssetError;
+use crate::tiled_map::{TileGrid, TiledMap};
+
+/// Tileset tile property turning the tile into a slope the duck walks along instead of a block.
+pub const SLOPE_PROPERTY: &str = "slope";
+
+/// Floor of a sloped tile, as its height at the tile's left and right edges in tile heights.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slope {
+    left: f32,
+    right: f32,
+}
+
+impl Slope {
+    /// 45° up to the right.
+    pub const RISE: Slope = Slope::new(0.0, 1.0);
+    /// 45° down to the right.
+    pub const FALL: Slope = Slope::new(1.0, 0.0);
+    /// 22.5° up to the right, as the lower half of a two tile ramp.
+    pub const RISE_LOW: Slope = Slope::new(0.0, 0.5);
+    pub const RISE_HIGH: Slope = Slope::new(0.5, 1.0);
+    /// 22.5° down to the right, as the upper half of a two tile ramp.
+    pub const FALL_HIGH: Slope = Slope::new(1.0, 0.5);
+    pub const FALL_LOW: Slope = Slope::new(0.5, 0.0);
+    pub const ALL: [(&'static str, Slope); 6] = [
+        ("rise", Slope::RISE),
+        ("fall", Slope::FALL),
+        ("rise-low", Slope::RISE_LOW),
+        ("rise-high", Slope::RISE_HIGH),
+        ("fall-high", Slope::FALL_HIGH),
+        ("fall-low", Slope::FALL_LOW),
+    ];
+
+    const fn new(left: f32, right: f32) -> Self {
+        Self { left, right }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find_map(|(slope_name, slope)| (slope_name == name).then_some(slope))
+    }
+
+    /// Floor height `x` of the way across the tile, in tile heights.
+    fn height_at(self, x: f32) -> f32 {
+        self.left + (self.right - self.left) * x
+    }
+}
+
+/// Grid of sloped tiles across all layers.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Slopes {
+    cells: Vec<Option<Slope>>,
+    grid: TileGrid,
+}
+
+impl Slopes {
+    pub fn new(cells: Vec<Option<Slope>>, grid: TileGrid) -> Self {
+        Self { cells, grid }
+    }
+
+    pub fn from_map(tiled_map: &TiledMap, map_path: &str) -> Result<Self, AssetError> {
+        let grid = tiled_map.grid();
+        let mut cells = vec![None; grid.len()];
+        for (x, y, name) in tiled_map.tiles_with_property(SLOPE_PROPERTY) {
+            let slope = name.as_str().and_then(Slope::from_name).ok_or_else(|| {
+                AssetError::InvalidProperty {
+                    path: map_path.into(),
+                    cause: format!(
+                        "tile at ({}, {}) has {}={}, expected rise, fall, rise-low, \
+                         rise-high, fall-high or fall-low",
+                        x, y, SLOPE_PROPERTY, name
+                    ),
+                }
+            })?;
+            cells[grid.index(x, y)] = Some(slope);
+        }
+        Ok(Self::new(cells, grid))
+    }
+
+    /// The highest slope floor under `rect` that's at most `reach` pixels above or below its
+    /// bottom edge. The rect rests on its highest corner, so it can walk off a slope onto a
+    /// block as high as the slope's top.
+    pub fn floor_under(&self, rect: Rect, reach: f32) -> Option<f32> {
+        let tile_size = self.grid.tile_size();
+        let band = Rect::new(rect.x, rect.bottom() - reach, rect.w, 2.0 * reach);
+        self.grid
+            .cells_overlapping(band)
+            .filter_map(|(x, y)| {
+                let slope = self.cells[self.grid.index(x, y)]?;
+                let tile_left = x as f32 * tile_size.x;
+                let across = |edge: f32| ((edge - tile_left) / tile_size.x).clamp(0.0, 1.0);
+                let top = slope
+                    .height_at(across(rect.left()))
+                    .max(slope.height_at(across(rect.right())));
+                Some((y as f32 + 1.0 - top) * tile_size.y)
+            })
+            .filter(|floor| (floor - rect.bottom()).abs() <= reach)
+            .min_by(f32::total_cmp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tiled_map::test_maps::*;
+
+    /// The desert tileset's 45° dune, rising to the right.
+    const RISE: u32 = 49;
+    /// The lower half of the desert tileset's 22.5° dune.
+    const RISE_LOW: u32 = 51;
+
+    #[test]
+    fn should_rest_rect_on_highest_point_of_slope() {
+        let slopes = Slopes::from_map(
+            &ground_map(3, &[RISE, 0, RISE_LOW], DESERT_TILESET_JSON),
+            "map.json",
+        )
+        .unwrap();
+        let floor_at =
+            |x, bottom: f32| slopes.floor_under(Rect::new(x, bottom - 10.0, 8.0, 10.0), 8.0);
+        assert_eq!(floor_at(0.0, 28.0), Some(24.0));
+        assert_eq!(floor_at(12.0, 14.0), Some(12.0));
+        assert_eq!(floor_at(28.0, 0.0), Some(0.0));
+        assert_eq!(floor_at(36.0, 0.0), None);
+        assert_eq!(floor_at(72.0, 28.0), Some(24.0));
+        assert_eq!(floor_at(0.0, 8.0), None);
+    }
+
+    #[test]
+    fn should_reject_unknown_slope() {
+        let tileset_json =
+            DESERT_TILESET_JSON.replacen(r#""value":"rise""#, r#""value":"cliff""#, 1);
+        let err = Slopes::from_map(&ground_map(3, &[RISE, 0, 0], &tileset_json), "map.json")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("slope=cliff"));
+    }
+}
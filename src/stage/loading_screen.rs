@@ -0,0 +1,20 @@
+use macroquad::prelude::*;
+
+const FONT_SIZE: f32 = 48.0;
+const DOTS_PER_SECOND: f64 = 3.0;
+
+pub fn draw() {
+    set_default_camera();
+    clear_background(BLACK);
+
+    let dots = (get_time() * DOTS_PER_SECOND) as usize % 4;
+    let text = format!("Loading{:<3}", ".".repeat(dots));
+    let size = measure_text(&text, None, FONT_SIZE as _, 1.0);
+    draw_text(
+        &text,
+        (screen_width() - size.width) / 2.0,
+        (screen_height() + size.height) / 2.0,
+        FONT_SIZE,
+        WHITE,
+    );
+}
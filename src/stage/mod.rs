@@ -0,0 +1,242 @@
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Waker};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use macroquad::prelude::next_frame;
+
+use crate::input_service::InputService;
+
+mod loading_screen;
+
+#[async_trait(?Send)]
+pub trait Stage {
+    async fn load(&mut self) {}
+    fn tick(&mut self, input: &InputService, frame_time: Duration) -> Transition;
+    fn draw(&mut self);
+    fn destroy(self: Box<Self>) {}
+
+    /// Overlays are drawn on top of the stage below them instead of replacing it.
+    fn is_overlay(&self) -> bool {
+        false
+    }
+}
+
+pub enum Transition {
+    None,
+    Push(Box<dyn Stage>),
+    Pop,
+    Replace(Box<dyn Stage>),
+    Quit,
+}
+
+/// Stack of stages: only the top one ticks, overlays let the ones below keep drawing.
+#[derive(Default)]
+pub struct StageManager {
+    stack: Vec<Box<dyn Stage>>,
+}
+
+impl StageManager {
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub async fn push(&mut self, mut stage: Box<dyn Stage>) {
+        load_with_loading_screen(stage.as_mut()).await;
+        self.stack.push(stage);
+    }
+
+    pub fn pop(&mut self) {
+        if let Some(stage) = self.stack.pop() {
+            stage.destroy();
+        }
+    }
+
+    pub async fn replace(&mut self, stage: Box<dyn Stage>) {
+        self.pop();
+        self.push(stage).await;
+    }
+
+    pub fn clear(&mut self) {
+        while !self.stack.is_empty() {
+            self.pop();
+        }
+    }
+
+    pub fn tick(&mut self, input: &InputService, frame_time: Duration) -> Transition {
+        match self.stack.last_mut() {
+            Some(stage) => stage.tick(input, frame_time),
+            None => Transition::Quit,
+        }
+    }
+
+    /// Applies a transition; returns `false` once there is nothing left to run.
+    pub async fn apply(&mut self, transition: Transition) -> bool {
+        match transition {
+            Transition::None => {}
+            Transition::Push(stage) => self.push(stage).await,
+            Transition::Pop => self.pop(),
+            Transition::Replace(stage) => self.replace(stage).await,
+            Transition::Quit => self.clear(),
+        }
+        !self.stack.is_empty()
+    }
+
+    pub fn draw(&mut self) {
+        let bottom = self
+            .stack
+            .iter()
+            .rposition(|stage| !stage.is_overlay())
+            .unwrap_or(0);
+        for stage in &mut self.stack[bottom..] {
+            stage.draw();
+        }
+    }
+}
+
+/// Polls `load` once per frame, drawing a loading screen until it completes.
+async fn load_with_loading_screen(stage: &mut dyn Stage) {
+    let mut load = pin!(stage.load());
+    let mut context = Context::from_waker(Waker::noop());
+    while load.as_mut().poll(&mut context).is_pending() {
+        loading_screen::draw();
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::task::Poll;
+
+    use super::*;
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    struct FakeStage {
+        name: &'static str,
+        log: Log,
+        overlay: bool,
+    }
+
+    impl FakeStage {
+        fn boxed(name: &'static str, log: &Log) -> Box<dyn Stage> {
+            Box::new(Self {
+                name,
+                log: log.clone(),
+                overlay: false,
+            })
+        }
+
+        fn overlay(name: &'static str, log: &Log) -> Box<dyn Stage> {
+            Box::new(Self {
+                name,
+                log: log.clone(),
+                overlay: true,
+            })
+        }
+
+        fn record(&self, event: &str) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", event, self.name));
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl Stage for FakeStage {
+        async fn load(&mut self) {
+            self.record("load");
+        }
+
+        fn tick(&mut self, _input: &InputService, _frame_time: Duration) -> Transition {
+            self.record("tick");
+            Transition::None
+        }
+
+        fn draw(&mut self) {
+            self.record("draw");
+        }
+
+        fn destroy(self: Box<Self>) {
+            self.record("destroy");
+        }
+
+        fn is_overlay(&self) -> bool {
+            self.overlay
+        }
+    }
+
+    /// Stages in these tests load synchronously, so one poll finishes every transition.
+    fn run<T>(future: impl Future<Output = T>) -> T {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future is still pending"),
+        }
+    }
+
+    fn take(log: &Log) -> Vec<String> {
+        std::mem::take(&mut *log.lock().unwrap())
+    }
+
+    #[test]
+    fn should_load_on_push_and_destroy_on_pop() {
+        let log = Log::default();
+        let mut stages = StageManager::default();
+
+        run(stages.push(FakeStage::boxed("title", &log)));
+        run(stages.apply(Transition::Pop));
+
+        assert_eq!(take(&log), ["load title", "destroy title"]);
+        assert!(stages.is_empty());
+    }
+
+    #[test]
+    fn should_destroy_replaced_stage_before_loading_next() {
+        let log = Log::default();
+        let mut stages = StageManager::default();
+        run(stages.push(FakeStage::boxed("title", &log)));
+        take(&log);
+
+        let running = run(stages.apply(Transition::Replace(FakeStage::boxed("game", &log))));
+
+        assert!(running);
+        assert_eq!(take(&log), ["destroy title", "load game"]);
+        assert_eq!(stages.len(), 1);
+    }
+
+    #[test]
+    fn should_tick_top_and_draw_through_overlays() {
+        let log = Log::default();
+        let mut stages = StageManager::default();
+        run(stages.push(FakeStage::boxed("title", &log)));
+        run(stages.push(FakeStage::boxed("game", &log)));
+        run(stages.push(FakeStage::overlay("pause", &log)));
+        take(&log);
+
+        stages.tick(&InputService::default(), Duration::ZERO);
+        stages.draw();
+
+        assert_eq!(take(&log), ["tick pause", "draw game", "draw pause"]);
+    }
+
+    #[test]
+    fn should_destroy_everything_on_quit() {
+        let log = Log::default();
+        let mut stages = StageManager::default();
+        run(stages.push(FakeStage::boxed("title", &log)));
+        run(stages.push(FakeStage::boxed("game", &log)));
+        take(&log);
+
+        let running = run(stages.apply(Transition::Quit));
+
+        assert!(!running);
+        assert_eq!(take(&log), ["destroy game", "destroy title"]);
+    }
+}
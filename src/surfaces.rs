@@ -0,0 +1,150 @@
+use macroquad::prelude::Rect;
+
+use crate::assets_server::AssetError;
+use crate::physics::Velocity;
+use crate::tiled_map::{TileGrid, TiledMap};
+
+/// Tileset tile property naming what the tile is made of.
+pub const SURFACE_PROPERTY: &str = "surface";
+/// Share of the usual deceleration the duck gets on ice.
+const ICE_GRIP: f32 = 0.1;
+/// Share of the usual top speed the duck can walk at in mud.
+const MUD_SPEED: f32 = 0.4;
+/// How fast a spring sends the duck up, whatever it landed with.
+const SPRING_VELOCITY: Velocity = Velocity::from_meters_on_second(11.0);
+
+/// What the ground under the duck is made of.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Surface {
+    #[default]
+    Ground,
+    Ice,
+    Mud,
+    Spring,
+}
+
+impl Surface {
+    pub const ALL: [Surface; 4] = [Surface::Ground, Surface::Ice, Surface::Mud, Surface::Spring];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|surface| surface.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Surface::Ground => "ground",
+            Surface::Ice => "ice",
+            Surface::Mud => "mud",
+            Surface::Spring => "spring",
+        }
+    }
+
+    /// Scales how fast the duck slows down once it stops walking.
+    pub fn grip(self) -> f32 {
+        match self {
+            Surface::Ice => ICE_GRIP,
+            _ => 1.0,
+        }
+    }
+
+    /// Scales the fastest the duck can walk.
+    pub fn speed(self) -> f32 {
+        match self {
+            Surface::Mud => MUD_SPEED,
+            _ => 1.0,
+        }
+    }
+
+    /// The upward velocity the surface launches the duck with as soon as it stands on it.
+    pub fn launch_velocity(self) -> Option<Velocity> {
+        match self {
+            Surface::Spring => Some(SPRING_VELOCITY),
+            _ => None,
+        }
+    }
+}
+
+/// Grid of tile surfaces across all layers; tiles without the property are plain ground.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Surfaces {
+    cells: Vec<Surface>,
+    grid: TileGrid,
+}
+
+impl Surfaces {
+    pub fn new(cells: Vec<Surface>, grid: TileGrid) -> Self {
+        Self { cells, grid }
+    }
+
+    pub fn from_map(tiled_map: &TiledMap, map_path: &str) -> Result<Self, AssetError> {
+        let grid = tiled_map.grid();
+        let mut cells = vec![Surface::Ground; grid.len()];
+        for (x, y, name) in tiled_map.tiles_with_property(SURFACE_PROPERTY) {
+            let surface = name.as_str().and_then(Surface::from_name).ok_or_else(|| {
+                AssetError::InvalidProperty {
+                    path: map_path.into(),
+                    cause: format!(
+                        "tile at ({}, {}) has {}={}, expected ground, ice, mud or spring",
+                        x, y, SURFACE_PROPERTY, name
+                    ),
+                }
+            })?;
+            cells[grid.index(x, y)] = surface;
+        }
+        Ok(Self::new(cells, grid))
+    }
+
+    /// The surface of the tiles right under `rect`'s bottom edge; any special one wins over
+    /// plain ground when the rect straddles several.
+    pub fn under(&self, rect: Rect) -> Surface {
+        let row = ((rect.bottom() + 1.0) / self.grid.tile_size().y).floor();
+        if row < 0.0 || row as usize >= self.grid.height() {
+            return Surface::Ground;
+        }
+        self.grid
+            .columns(rect.left(), rect.right())
+            .map(|x| self.cells[self.grid.index(x, row as usize)])
+            .find(|surface| *surface != Surface::Ground)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tiled_map::test_maps::*;
+
+    /// The desert tileset's pale slab, marked as ice.
+    const ICE: u32 = 42;
+    /// The desert tileset's spring brick.
+    const SPRING: u32 = 37;
+
+    /// Open air over a row of `floor` tiles.
+    fn map(floor: [u32; 3], tileset_json: &str) -> TiledMap {
+        let [left, middle, right] = floor;
+        ground_map(3, &[0, 0, 0, left, middle, right], tileset_json)
+    }
+
+    #[test]
+    fn should_find_surface_under_feet() {
+        let surfaces =
+            Surfaces::from_map(&map([1, ICE, SPRING], DESERT_TILESET_JSON), "map.json").unwrap();
+        let standing_at = |x| surfaces.under(Rect::new(x, 0.0, 24.0, 31.0));
+        assert_eq!(standing_at(0.0), Surface::Ground);
+        assert_eq!(standing_at(20.0), Surface::Ice);
+        assert_eq!(standing_at(72.0), Surface::Spring);
+        assert_eq!(
+            surfaces.under(Rect::new(40.0, 0.0, 24.0, 16.0)),
+            Surface::Ground
+        );
+    }
+
+    #[test]
+    fn should_reject_unknown_surface() {
+        let tileset_json = DESERT_TILESET_JSON.replacen(r#""value":"ice""#, r#""value":"lava""#, 1);
+        let err = Surfaces::from_map(&map([ICE, 1, 1], &tileset_json), "map.json")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("surface=lava"));
+    }
+}
@@ -0,0 +1,28 @@
+//! Tile worlds for tests that move actors around, all laid out on one grid.
+
+use macroquad::prelude::vec2;
+use macroquad_platformer::{Tile, World};
+
+use crate::tiled_map::TileGrid;
+
+pub const TILE_SIZE: f32 = 32.0;
+pub const WIDTH: usize = 40;
+pub const HEIGHT: usize = 20;
+/// Top of the bottom row, where a floor along the bottom of the map starts.
+pub const FLOOR_Y: f32 = (HEIGHT - 1) as f32 * TILE_SIZE;
+
+pub fn grid() -> TileGrid {
+    TileGrid::new(WIDTH, HEIGHT, vec2(TILE_SIZE, TILE_SIZE))
+}
+
+/// A world whose only static colliders are `colliders`, laid out row by row on `grid()`.
+pub fn world_from(colliders: Vec<Tile>) -> World {
+    assert_eq!(
+        colliders.len(),
+        grid().len(),
+        "colliders must cover the grid"
+    );
+    let mut world = World::new();
+    world.add_static_tiled_layer(colliders, TILE_SIZE, TILE_SIZE, WIDTH, 1);
+    world
+}
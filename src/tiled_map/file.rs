@@ -0,0 +1,151 @@
+use std::str::Chars;
+
+use nanoserde::{DeJson, DeJsonErr, DeJsonState, DeJsonTok, SerJson, SerJsonState};
+
+use super::PropertyValue;
+
+pub use self::layer::LayerFile;
+
+/// The part of Tiled's JSON map format the game reads.
+///
+/// macroquad_tiled only understands tile layers and untyped properties, so this is parsed
+/// first and written back with the properties and object layers taken out.
+#[derive(DeJson, SerJson)]
+pub struct MapFile {
+    pub width: u32,
+    pub height: u32,
+    pub tilewidth: u32,
+    pub tileheight: u32,
+    #[nserde(default)]
+    pub orientation: String,
+    #[nserde(default)]
+    pub renderorder: String,
+    pub layers: Vec<LayerFile>,
+    pub tilesets: Vec<TilesetRefFile>,
+    #[nserde(default)]
+    pub properties: Vec<PropertyFile>,
+}
+
+#[derive(DeJson, SerJson)]
+pub struct TilesetRefFile {
+    pub firstgid: u32,
+    #[nserde(default)]
+    pub source: String,
+}
+
+/// An external tileset; like the map, it's written back without custom properties.
+#[derive(DeJson, SerJson)]
+pub struct TilesetFile {
+    pub name: String,
+    pub image: String,
+    pub imagewidth: u32,
+    pub imageheight: u32,
+    pub columns: u32,
+    pub tilecount: u32,
+    pub tilewidth: u32,
+    pub tileheight: u32,
+    #[nserde(default)]
+    pub margin: u32,
+    #[nserde(default)]
+    pub spacing: u32,
+    #[nserde(default)]
+    pub tiles: Vec<TileFile>,
+    #[nserde(default)]
+    pub properties: Vec<PropertyFile>,
+}
+
+#[derive(DeJson, SerJson)]
+pub struct TileFile {
+    pub id: u32,
+    #[nserde(rename = "type")]
+    #[nserde(default)]
+    pub ty: String,
+    #[nserde(default)]
+    pub properties: Vec<PropertyFile>,
+}
+
+#[derive(DeJson, SerJson)]
+pub struct ObjectFile {
+    #[nserde(default)]
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+    #[nserde(default)]
+    pub width: f32,
+    #[nserde(default)]
+    pub height: f32,
+    #[nserde(default)]
+    pub polyline: Vec<PointFile>,
+    #[nserde(default)]
+    pub properties: Vec<PropertyFile>,
+}
+
+/// A polyline vertex, relative to its object's position.
+#[derive(DeJson, SerJson)]
+pub struct PointFile {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(DeJson, SerJson)]
+pub struct PropertyFile {
+    pub name: String,
+    pub value: PropertyValue,
+}
+
+// nanoserde's derive expands `Option` fields into matches clippy would rather see as `?`, and
+// only an allow on an enclosing module reaches the impls it generates
+#[allow(clippy::question_mark)]
+mod layer {
+    use nanoserde::{DeJson, SerJson};
+
+    use super::{ObjectFile, PropertyFile};
+
+    #[derive(DeJson, SerJson)]
+    pub struct LayerFile {
+        pub name: String,
+        #[nserde(rename = "type")]
+        pub ty: String,
+        #[nserde(default)]
+        pub width: u32,
+        #[nserde(default)]
+        pub height: u32,
+        #[nserde(default)]
+        pub data: Vec<u32>,
+        #[nserde(default)]
+        pub objects: Vec<ObjectFile>,
+        /// An `Option` so it's left out once taken: macroquad_tiled reads layer properties as an
+        /// object, not a list, and would reject even an empty one.
+        pub properties: Option<Vec<PropertyFile>>,
+    }
+
+    impl LayerFile {
+        pub const TILE_LAYER: &'static str = "tilelayer";
+    }
+}
+
+impl DeJson for PropertyValue {
+    fn de_json(s: &mut DeJsonState, i: &mut Chars) -> Result<Self, DeJsonErr> {
+        let value = match s.tok {
+            DeJsonTok::Str => PropertyValue::String(s.as_string()?),
+            DeJsonTok::Bool(value) => PropertyValue::Bool(value),
+            DeJsonTok::U64(value) => PropertyValue::Int(value as i64),
+            DeJsonTok::I64(value) => PropertyValue::Int(value),
+            DeJsonTok::F64(value) => PropertyValue::Float(value),
+            _ => return Err(s.err_token("string, bool or number")),
+        };
+        s.next_tok(i)?;
+        Ok(value)
+    }
+}
+
+impl SerJson for PropertyValue {
+    fn ser_json(&self, d: usize, s: &mut SerJsonState) {
+        match self {
+            PropertyValue::String(value) => value.ser_json(d, s),
+            PropertyValue::Bool(value) => value.ser_json(d, s),
+            PropertyValue::Int(value) => value.ser_json(d, s),
+            PropertyValue::Float(value) => value.ser_json(d, s),
+        }
+    }
+}
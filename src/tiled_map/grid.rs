@@ -0,0 +1,100 @@
+use std::ops::Range;
+
+use macroquad::prelude::{vec2, Rect, Vec2};
+
+/// A map's tile grid: how many cells it has and how much of the world each one covers.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TileGrid {
+    width: usize,
+    height: usize,
+    tile_size: Vec2,
+}
+
+impl TileGrid {
+    pub fn new(width: usize, height: usize, tile_size: Vec2) -> Self {
+        Self {
+            width,
+            height,
+            tile_size,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn tile_size(&self) -> Vec2 {
+        self.tile_size
+    }
+
+    /// World size the grid covers.
+    pub fn size(&self) -> Vec2 {
+        vec2(self.width as f32, self.height as f32) * self.tile_size
+    }
+
+    /// Number of cells, for a `Vec` holding one value per cell.
+    pub fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Where the cell at column `x`, row `y` sits in a `Vec` laid out row by row.
+    pub fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    /// Every cell as `(x, y)`, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Cells `rect` overlaps, leaving out any part of it outside the grid.
+    pub fn cells_overlapping(&self, rect: Rect) -> impl Iterator<Item = (usize, usize)> {
+        let columns = self.columns(rect.left(), rect.right());
+        self.rows(rect.top(), rect.bottom())
+            .flat_map(move |y| columns.clone().map(move |x| (x, y)))
+    }
+
+    /// Columns spanning `left..right` in world space.
+    pub fn columns(&self, left: f32, right: f32) -> Range<usize> {
+        span(left, right, self.tile_size.x, self.width)
+    }
+
+    /// Rows spanning `top..bottom` in world space.
+    pub fn rows(&self, top: f32, bottom: f32) -> Range<usize> {
+        span(top, bottom, self.tile_size.y, self.height)
+    }
+}
+
+fn span(start: f32, end: f32, tile: f32, len: usize) -> Range<usize> {
+    let first = ((start / tile).floor().max(0.0) as usize).min(len);
+    let last = ((end / tile).ceil().max(0.0) as usize).min(len);
+    first..last.max(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_clamp_overlapping_cells_to_grid() {
+        let grid = TileGrid::new(3, 2, vec2(32.0, 32.0));
+        let cells = |rect| grid.cells_overlapping(rect).collect::<Vec<_>>();
+        assert_eq!(cells(Rect::new(16.0, 0.0, 32.0, 32.0)), [(0, 0), (1, 0)]);
+        assert_eq!(
+            cells(Rect::new(-40.0, 40.0, 60.0, 100.0)),
+            [(0, 1)],
+            "only the part inside the grid counts"
+        );
+        assert!(cells(Rect::new(200.0, 0.0, 10.0, 10.0)).is_empty());
+        assert_eq!(grid.cells().last(), Some((2, 1)));
+    }
+}
@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use macroquad::prelude::{vec2, Rect, Texture2D, Vec2};
+use macroquad_tiled::{Map, Tile};
+use nanoserde::{DeJson, SerJson};
+
+pub use self::grid::TileGrid;
+
+use self::file::*;
+use crate::assets_server::AssetError;
+
+mod file;
+mod grid;
+#[cfg(test)]
+pub mod test_maps;
+
+/// A custom property value as typed in Tiled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    String(String),
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+}
+
+impl PropertyValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Ints and floats alike, since Tiled users rarely care which one they typed.
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            PropertyValue::Int(value) => Some(*value as f32),
+            PropertyValue::Float(value) => Some(*value as f32),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::String(value) => write!(f, "{}", value),
+            PropertyValue::Bool(value) => write!(f, "{}", value),
+            PropertyValue::Int(value) => write!(f, "{}", value),
+            PropertyValue::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+pub type Properties = HashMap<String, PropertyValue>;
+
+fn to_properties(properties: Vec<PropertyFile>) -> Properties {
+    properties
+        .into_iter()
+        .map(|property| (property.name, property.value))
+        .collect()
+}
+
+/// A point or rectangle placed on one of the map's object layers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapObject {
+    pub name: String,
+    pub layer: String,
+    /// World-space bounds; zero-sized for point and polyline objects.
+    pub rect: Rect,
+    /// World-space vertices of a polyline object, empty for every other shape.
+    pub polyline: Vec<Vec2>,
+    pub properties: Properties,
+}
+
+pub struct TiledMap {
+    m_map: Map,
+    properties: Properties,
+    tile_layers: Vec<String>,
+    layer_properties: HashMap<String, Properties>,
+    /// Keyed by tileset name, then by tile id within the tileset.
+    tile_properties: HashMap<String, HashMap<u32, Properties>>,
+    objects: Vec<MapObject>,
+}
+
+impl std::ops::Deref for TiledMap {
+    type Target = Map;
+
+    fn deref(&self) -> &Self::Target {
+        &self.m_map
+    }
+}
+
+impl TiledMap {
+    /// Loads a Tiled JSON map whose tilesets are external files, given as `(path, json)`.
+    pub fn parse(
+        map_path: &str,
+        map_json: &str,
+        external_tilesets: &[(&str, &str)],
+        textures: &[(&str, Texture2D)],
+    ) -> Result<Self, AssetError> {
+        let mut map_file =
+            MapFile::deserialize_json(map_json).map_err(|err| AssetError::parse(map_path, err))?;
+
+        // macroquad_tiled panics on an unknown external tileset, so check the references first
+        for tileset in &map_file.tilesets {
+            if !external_tilesets
+                .iter()
+                .any(|(path, _)| *path == tileset.source)
+            {
+                let cause = if tileset.source.is_empty() {
+                    "embedded tilesets aren't supported, export it to a file".to_string()
+                } else {
+                    format!("unknown tileset \"{}\"", tileset.source)
+                };
+                return Err(AssetError::TilesetMismatch {
+                    path: map_path.into(),
+                    cause,
+                });
+            }
+        }
+
+        let mut tile_properties = HashMap::new();
+        let mut stripped_tilesets = vec![];
+        for (path, json) in external_tilesets {
+            let mut tileset =
+                TilesetFile::deserialize_json(json).map_err(|err| AssetError::parse(path, err))?;
+            tileset.properties.clear();
+            let properties = tile_properties
+                .entry(tileset.name.clone())
+                .or_insert_with(HashMap::new);
+            for tile in &mut tileset.tiles {
+                properties.insert(tile.id, to_properties(std::mem::take(&mut tile.properties)));
+            }
+            stripped_tilesets.push((*path, tileset.serialize_json()));
+        }
+        let stripped_tilesets = stripped_tilesets
+            .iter()
+            .map(|(path, json)| (*path, json.as_str()))
+            .collect::<Vec<_>>();
+
+        let properties = to_properties(std::mem::take(&mut map_file.properties));
+        let mut tile_layers = vec![];
+        let mut layer_properties = HashMap::new();
+        let mut objects = vec![];
+        for layer in &mut map_file.layers {
+            layer_properties.insert(
+                layer.name.clone(),
+                to_properties(layer.properties.take().unwrap_or_default()),
+            );
+            if layer.ty == LayerFile::TILE_LAYER {
+                tile_layers.push(layer.name.clone());
+            }
+            for object in std::mem::take(&mut layer.objects) {
+                let origin = vec2(object.x, object.y);
+                objects.push(MapObject {
+                    name: object.name,
+                    layer: layer.name.clone(),
+                    rect: Rect::new(object.x, object.y, object.width, object.height),
+                    polyline: object
+                        .polyline
+                        .into_iter()
+                        .map(|point| origin + vec2(point.x, point.y))
+                        .collect(),
+                    properties: to_properties(object.properties),
+                });
+            }
+        }
+        map_file
+            .layers
+            .retain(|layer| layer.ty == LayerFile::TILE_LAYER);
+
+        let m_map =
+            macroquad_tiled::load_map(&map_file.serialize_json(), textures, &stripped_tilesets)
+                .map_err(|err| match err {
+                    // The map itself already parsed above, so this comes from a tileset
+                    macroquad_tiled::Error::DeJsonErr { .. } => {
+                        let tileset_path =
+                            external_tilesets.first().map_or(map_path, |(path, _)| path);
+                        AssetError::parse(tileset_path, err)
+                    }
+                    _ => AssetError::TilesetMismatch {
+                        path: map_path.into(),
+                        cause: err.to_string(),
+                    },
+                })?;
+
+        Ok(Self {
+            m_map,
+            properties,
+            tile_layers,
+            layer_properties,
+            tile_properties,
+            objects,
+        })
+    }
+
+    /// Custom properties set on the map itself.
+    pub fn properties(&self) -> &Properties {
+        &self.properties
+    }
+
+    /// Names of the tile layers, bottom to top.
+    pub fn tile_layers(&self) -> impl Iterator<Item = &str> {
+        self.tile_layers.iter().map(String::as_str)
+    }
+
+    pub fn grid(&self) -> TileGrid {
+        let raw_map = &self.m_map.raw_tiled_map;
+        TileGrid::new(
+            raw_map.width as usize,
+            raw_map.height as usize,
+            vec2(raw_map.tilewidth as f32, raw_map.tileheight as f32),
+        )
+    }
+
+    /// Every cell of `layer` as `(x, y, tile)`, row by row. Unlike `Map::tiles`, which skips a
+    /// layer's last tile, this one covers the whole grid.
+    pub fn layer_tiles<'a>(
+        &'a self,
+        layer: &'a str,
+    ) -> impl Iterator<Item = (usize, usize, Option<&'a Tile>)> + 'a {
+        self.grid().cells().map(move |(x, y)| {
+            let tile = self.m_map.get_tile(layer, x as u32, y as u32);
+            (x, y, tile.as_ref())
+        })
+    }
+
+    /// Cells of any tile layer whose tile has the `name` property, as `(x, y, value)`.
+    pub fn tiles_with_property<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = (usize, usize, &'a PropertyValue)> + 'a {
+        self.tile_layers().flat_map(move |layer| {
+            self.layer_tiles(layer)
+                .filter_map(move |(x, y, tile)| Some((x, y, self.tile_property(tile?, name)?)))
+        })
+    }
+
+    pub fn layer_property(&self, layer: &str, name: &str) -> Option<&PropertyValue> {
+        self.layer_properties.get(layer)?.get(name)
+    }
+
+    /// Custom property set on `tile` in its tileset.
+    pub fn tile_property(&self, tile: &Tile, name: &str) -> Option<&PropertyValue> {
+        self.tile_properties
+            .get(&tile.tileset)?
+            .get(&tile.id)?
+            .get(name)
+    }
+
+    pub fn objects(&self) -> &[MapObject] {
+        &self.objects
+    }
+
+    /// The first object called `name` on any object layer.
+    pub fn object(&self, name: &str) -> Option<&MapObject> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+
+    pub fn draw_all_layers_at_viewport(&self, viewport: Rect) {
+        let x_start = (viewport.left() / self.tile_width() - 1.0) as u32;
+        let x_end = (viewport.right() / self.tile_width() + 1.0) as u32;
+
+        let y_start = (viewport.top() / self.tile_height() - 1.0) as u32;
+        let y_end = (viewport.bottom() / self.tile_height() + 1.0) as u32;
+
+        for layer in self.m_map.raw_tiled_map.layers.iter() {
+            for x in x_start..=x_end {
+                for y in y_start..=y_end {
+                    self.draw_tile(&layer.name, x, y);
+                }
+            }
+        }
+    }
+
+    fn draw_tile(&self, layer: &str, x: u32, y: u32) {
+        if let Some(tile) = self.m_map.get_tile(layer, x, y) {
+            let pos = vec2(x as f32 * self.tile_width(), y as f32 * self.tile_height());
+            self.m_map.spr(
+                &tile.tileset,
+                tile.id,
+                Rect::new(pos.x, pos.y, self.tile_width(), self.tile_height()),
+            );
+        }
+    }
+
+    fn tile_width(&self) -> f32 {
+        self.m_map.raw_tiled_map.tilewidth as f32
+    }
+
+    fn tile_height(&self) -> f32 {
+        self.m_map.raw_tiled_map.tileheight as f32
+    }
+
+    pub fn size(&self) -> Vec2 {
+        vec2(self.width(), self.height())
+    }
+
+    fn width(&self) -> f32 {
+        (self.m_map.raw_tiled_map.width * self.m_map.raw_tiled_map.tilewidth) as f32
+    }
+
+    fn height(&self) -> f32 {
+        (self.m_map.raw_tiled_map.height * self.m_map.raw_tiled_map.tileheight) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILESET_PATH: &str = "tmw_desert_spacing.json";
+    const TILESET_JSON: &str = include_str!("../../assets/tmw_desert_spacing.json");
+
+    const MAP_JSON: &str = r#"{
+        "width": 2, "height": 1, "tilewidth": 32, "tileheight": 32,
+        "properties": [{"name": "title", "type": "string", "value": "Dunes"}],
+        "layers": [
+            {
+                "name": "Ground", "type": "tilelayer", "width": 2, "height": 1, "data": [1, 0],
+                "properties": [
+                    {"name": "collision", "type": "string", "value": "solid"},
+                    {"name": "friction", "type": "float", "value": 0.5},
+                    {"name": "depth", "type": "int", "value": 3},
+                    {"name": "hidden", "type": "bool", "value": false}
+                ]
+            },
+            {
+                "name": "Objects", "type": "objectgroup",
+                "objects": [
+                    {"name": "spawn", "point": true, "x": 16, "y": 32},
+                    {"name": "path", "x": 8, "y": 8, "polyline": [{"x": 0, "y": 0}, {"x": 24, "y": 0}]}
+                ]
+            }
+        ],
+        "tilesets": [{"firstgid": 1, "source": "tmw_desert_spacing.json"}]
+    }"#;
+
+    fn parse(map_json: &str, tileset_json: &str) -> Result<TiledMap, AssetError> {
+        TiledMap::parse(
+            "map.json",
+            map_json,
+            &[(TILESET_PATH, tileset_json)],
+            &[("tmw_desert_spacing.png", Texture2D::empty())],
+        )
+    }
+
+    #[test]
+    fn should_read_typed_properties() {
+        let map = parse(MAP_JSON, TILESET_JSON).unwrap();
+        let property = |name| map.layer_property("Ground", name);
+        assert_eq!(
+            property("collision").and_then(|v| v.as_str()),
+            Some("solid")
+        );
+        assert_eq!(property("friction").and_then(|v| v.as_f32()), Some(0.5));
+        assert_eq!(property("depth").and_then(|v| v.as_f32()), Some(3.0));
+        assert_eq!(property("hidden").and_then(|v| v.as_bool()), Some(false));
+        assert_eq!(
+            map.properties().get("title"),
+            Some(&PropertyValue::String("Dunes".into()))
+        );
+    }
+
+    #[test]
+    fn should_read_tile_properties_from_tileset() {
+        let tileset_json = TILESET_JSON.replacen(
+            "\"tilewidth\"",
+            r#""tiles": [{"id": 0, "properties": [{"name": "hazard", "type": "bool", "value": true}]}],
+            "tilewidth""#,
+            1,
+        );
+        let map = parse(MAP_JSON, &tileset_json).unwrap();
+        let tile = map.get_tile("Ground", 0, 0).as_ref().unwrap();
+        assert_eq!(
+            map.tile_property(tile, "hazard").and_then(|v| v.as_bool()),
+            Some(true)
+        );
+        assert_eq!(map.tile_property(tile, "friction"), None);
+    }
+
+    #[test]
+    fn should_walk_layer_up_to_its_last_tile() {
+        let map_json = MAP_JSON.replace(r#""data": [1, 0]"#, r#""data": [0, 1]"#);
+        let map = parse(&map_json, TILESET_JSON).unwrap();
+        let placed = map
+            .layer_tiles("Ground")
+            .filter(|(_, _, tile)| tile.is_some())
+            .map(|(x, y, _)| (x, y))
+            .collect::<Vec<_>>();
+        assert_eq!(placed, [(1, 0)]);
+    }
+
+    #[test]
+    fn should_keep_object_layers_out_of_tile_layers() {
+        let map = parse(MAP_JSON, TILESET_JSON).unwrap();
+        assert_eq!(map.tile_layers().collect::<Vec<_>>(), ["Ground"]);
+        assert!(!map.contains_layer("Objects"));
+
+        let spawn = map.object("spawn").unwrap();
+        assert_eq!(spawn.layer, "Objects");
+        assert_eq!(spawn.rect.point(), vec2(16.0, 32.0));
+        assert!(spawn.polyline.is_empty());
+        assert_eq!(
+            map.object("path").unwrap().polyline,
+            [vec2(8.0, 8.0), vec2(32.0, 8.0)]
+        );
+    }
+
+    #[test]
+    fn should_report_unknown_tileset() {
+        let map_json = MAP_JSON.replace(TILESET_PATH, "other_tileset.json");
+        let err = parse(&map_json, TILESET_JSON).err().unwrap();
+        assert!(matches!(err, AssetError::TilesetMismatch { .. }));
+        assert_eq!(err.path(), "map.json");
+    }
+
+    #[test]
+    fn should_attribute_parse_errors_to_file() {
+        let map_err = parse("{", TILESET_JSON).err().unwrap();
+        assert!(matches!(map_err, AssetError::Parse { .. }));
+        assert_eq!(map_err.path(), "map.json");
+
+        let tileset_err = parse(MAP_JSON, "{").err().unwrap();
+        assert!(matches!(tileset_err, AssetError::Parse { .. }));
+        assert_eq!(tileset_err.path(), TILESET_PATH);
+    }
+}
@@ -0,0 +1,26 @@
+//! Small maps for tests of modules that read tiles, built on the shipped desert tileset.
+
+use macroquad::prelude::Texture2D;
+
+use super::TiledMap;
+
+pub const DESERT_TILESET_JSON: &str = include_str!("../../assets/tmw_desert_spacing.json");
+
+/// A map with a single `Ground` layer, `width` tiles wide, holding `data` row by row. The desert
+/// tileset's JSON can be swapped for an edited copy to test broken tile properties.
+pub fn ground_map(width: usize, data: &[u32], tileset_json: &str) -> TiledMap {
+    let height = data.len() / width;
+    let map_json = format!(
+        r#"{{"width": {width}, "height": {height}, "tilewidth": 32, "tileheight": 32,
+            "layers": [{{"name": "Ground", "type": "tilelayer",
+                         "width": {width}, "height": {height}, "data": {data:?}}}],
+            "tilesets": [{{"firstgid": 1, "source": "tmw_desert_spacing.json"}}]}}"#,
+    );
+    TiledMap::parse(
+        "map.json",
+        &map_json,
+        &[("tmw_desert_spacing.json", tileset_json)],
+        &[("tmw_desert_spacing.png", Texture2D::empty())],
+    )
+    .unwrap()
+}
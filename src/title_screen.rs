@@ -0,0 +1,69 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use macroquad::prelude::*;
+
+use crate::campaign::SharedCampaign;
+use crate::input_service::{key_code_name, Action, InputService};
+use crate::level_select::LevelSelect;
+use crate::stage::{Stage, Transition};
+
+const TITLE: &str = "Duck Game";
+const TITLE_FONT_SIZE: f32 = 96.0;
+const HINT_FONT_SIZE: f32 = 32.0;
+
+pub struct TitleScreen {
+    campaign: SharedCampaign,
+    start_key: Option<KeyCode>,
+}
+
+impl TitleScreen {
+    pub fn new(campaign: SharedCampaign) -> Self {
+        Self {
+            campaign,
+            start_key: None,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Stage for TitleScreen {
+    fn tick(&mut self, input: &InputService, _frame_time: Duration) -> Transition {
+        self.start_key = input.bindings().keys(Action::Jump).first().copied();
+
+        if input.is_pressed(Action::Jump) {
+            Transition::Push(Box::new(LevelSelect::new(Rc::clone(&self.campaign))))
+        } else if input.is_pressed(Action::Pause) {
+            Transition::Quit
+        } else {
+            Transition::None
+        }
+    }
+
+    fn draw(&mut self) {
+        set_default_camera();
+        clear_background(SKYBLUE);
+
+        draw_centered_text(TITLE, screen_height() / 2.0, TITLE_FONT_SIZE);
+        if let Some(key) = self.start_key {
+            let hint = format!("Press {} to start", key_code_name(key));
+            draw_centered_text(
+                &hint,
+                screen_height() / 2.0 + TITLE_FONT_SIZE,
+                HINT_FONT_SIZE,
+            );
+        }
+    }
+}
+
+pub fn draw_centered_text(text: &str, y: f32, font_size: f32) {
+    let size = measure_text(text, None, font_size as _, 1.0);
+    draw_text(
+        text,
+        (screen_width() - size.width) / 2.0,
+        y,
+        font_size,
+        WHITE,
+    );
+}
@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use macroquad::prelude::*;
+
+use crate::duck_params::DuckParams;
+use crate::physics::*;
+use crate::tiled_map::TiledMap;
+
+/// Name of the Tiled rectangles the duck swims in.
+pub const WATER: &str = "water";
+
+const WATER_COLOR: Color = Color::new(0.2, 0.45, 0.85, 0.45);
+const DROPLET_COLOR: Color = Color::new(0.75, 0.88, 1.0, 0.9);
+const DROPLET_RADIUS: f32 = 3.0;
+const SPLASH_DROPLETS: usize = 12;
+const DROPLET_LIFETIME: Duration = Duration::from_millis(500);
+/// Droplets leave the surface this fast sideways, either way.
+const DROPLET_SPREAD: Velocity = Velocity::from_meters_on_second(1.5);
+/// Droplets leave the surface upwards somewhere between these speeds.
+const DROPLET_LIFT: [Velocity; 2] = [
+    Velocity::from_meters_on_second(1.5),
+    Velocity::from_meters_on_second(3.5),
+];
+
+/// The level's water volumes.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Water {
+    volumes: Vec<Rect>,
+}
+
+impl Water {
+    pub fn new(volumes: Vec<Rect>) -> Self {
+        Self { volumes }
+    }
+
+    pub fn from_map(tiled_map: &TiledMap) -> Self {
+        let volumes = tiled_map
+            .objects()
+            .iter()
+            .filter(|object| object.name == WATER)
+            .map(|object| object.rect)
+            .collect();
+        Self { volumes }
+    }
+
+    /// The first volume `rect` is in.
+    pub fn volume_overlapping(&self, rect: Rect) -> Option<Rect> {
+        self.volumes.iter().copied().find(|volume| {
+            volume
+                .intersect(rect)
+                .is_some_and(|overlap| overlap.h > 0.0)
+        })
+    }
+
+    /// How much of `rect`'s height is under water, from 0 when dry to 1 when fully under.
+    pub fn submersion(&self, rect: Rect) -> f32 {
+        self.volumes
+            .iter()
+            .filter_map(|volume| volume.intersect(rect))
+            .map(|overlap| overlap.h / rect.h)
+            .fold(0.0, f32::max)
+    }
+
+    /// Drawn over everything that's in the water, so it looks submerged.
+    pub fn draw(&self, viewport: Rect) {
+        for volume in self
+            .volumes
+            .iter()
+            .filter(|volume| volume.overlaps(&viewport))
+        {
+            draw_rectangle(volume.x, volume.y, volume.w, volume.h, WATER_COLOR);
+        }
+    }
+}
+
+struct Droplet {
+    /// Where the splash happened; the body moves relative to it.
+    origin: Vec2,
+    body: Body,
+    age: Duration,
+}
+
+impl Droplet {
+    fn pos(&self) -> Vec2 {
+        self.origin + WORLD_SCALE.xy_to_pixels(self.body.position)
+    }
+}
+
+/// Droplets thrown up where the duck breaks the surface.
+#[derive(Default)]
+pub struct Splashes {
+    droplets: Vec<Droplet>,
+}
+
+impl Splashes {
+    pub fn spawn(&mut self, pos: Vec2) {
+        for _ in 0..SPLASH_DROPLETS {
+            let spread = DROPLET_SPREAD.as_meters_on_second();
+            let [min_lift, max_lift] = DROPLET_LIFT.map(|lift| lift.as_meters_on_second());
+            let velocity = XY {
+                x: Velocity::from_meters_on_second(rand::gen_range(-spread, spread)),
+                y: Velocity::from_meters_on_second(-rand::gen_range(min_lift, max_lift)),
+            };
+            self.droplets.push(Droplet {
+                origin: pos,
+                body: Body::new(XY::default(), velocity),
+                age: Duration::ZERO,
+            });
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.droplets.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.droplets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.droplets.is_empty()
+    }
+
+    pub fn step(&mut self, frame_time: Duration) {
+        let gravity = DuckParams::default().gravity_acceleration;
+        for droplet in &mut self.droplets {
+            droplet
+                .body
+                .advance(XY::new(Acceleration::ZERO, gravity), frame_time);
+            droplet.age += frame_time;
+        }
+        self.droplets
+            .retain(|droplet| droplet.age < DROPLET_LIFETIME);
+    }
+
+    pub fn draw(&self) {
+        for droplet in &self.droplets {
+            let fade = 1.0 - droplet.age.as_secs_f32() / DROPLET_LIFETIME.as_secs_f32();
+            let color = Color {
+                a: DROPLET_COLOR.a * fade,
+                ..DROPLET_COLOR
+            };
+            let pos = droplet.pos();
+            draw_circle(pos.x, pos.y, DROPLET_RADIUS, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_measure_submersion_against_deepest_volume() {
+        let water = Water::new(vec![
+            Rect::new(0.0, 100.0, 100.0, 100.0),
+            Rect::new(200.0, 50.0, 100.0, 150.0),
+        ]);
+        assert_eq!(water.submersion(Rect::new(10.0, 20.0, 20.0, 40.0)), 0.0);
+        assert_eq!(water.submersion(Rect::new(10.0, 90.0, 20.0, 40.0)), 0.75);
+        assert_eq!(water.submersion(Rect::new(190.0, 40.0, 20.0, 40.0)), 0.75);
+        assert_eq!(water.submersion(Rect::new(10.0, 150.0, 20.0, 40.0)), 1.0);
+        assert_eq!(
+            water.volume_overlapping(Rect::new(250.0, 0.0, 20.0, 60.0)),
+            Some(Rect::new(200.0, 50.0, 100.0, 150.0))
+        );
+        assert_eq!(
+            water.volume_overlapping(Rect::new(10.0, 60.0, 20.0, 40.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn should_fly_droplets_up_and_let_them_fade() {
+        let mut splashes = Splashes::default();
+        splashes.spawn(vec2(0.0, 0.0));
+        assert_eq!(splashes.len(), SPLASH_DROPLETS);
+
+        splashes.step(Duration::from_millis(50));
+        assert!(splashes
+            .droplets
+            .iter()
+            .all(|droplet| droplet.pos().y < 0.0));
+        splashes.step(DROPLET_LIFETIME);
+        assert!(splashes.is_empty());
+    }
+}